@@ -0,0 +1,564 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Crowdloan Rewards Batch Generator
+//!
+//! Off-chain helper to build the calls that initialize the crowdloan rewards pallet.
+//!
+//! Initializing the pallet means dispatching as many `initialize_reward_vec` calls as needed to
+//! insert every contribution (each of them bounded by `MaxInitContributors`), and then
+//! `complete_initialization`. This crate takes a contribution file and a few parameters of the
+//! target runtime and produces the SCALE-encoded call data for every `utility::batch_all` of
+//! `initialize_reward_vec` calls, the final `complete_initialization` call, and a summary with
+//! checksums so the governance proposal can be reviewed and reproduced by anyone.
+//!
+//! The contribution file has one contribution per line: `relay_account,native_account,reward`.
+//! Accounts are hex encoded (with or without the `0x` prefix), the native account is left empty
+//! for contributions that still need to be associated, and the reward is a decimal amount.
+//! Empty lines and lines starting with `#` are ignored.
+//!
+//! The generated calls assume the runtime uses a 32 byte `RelayChainAccountId`, a `u128`
+//! `Balance` and a `u32` `VestingBlockNumber`, which is the case for any relay chain based setup.
+
+use parity_scale_codec::{Compact, Encode, Output};
+use sp_core_hashing::blake2_256;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Call index of `complete_initialization` in the crowdloan rewards pallet
+pub const COMPLETE_INITIALIZATION_CALL_INDEX: u8 = 4;
+/// Call index of `initialize_reward_vec` in the crowdloan rewards pallet
+pub const INITIALIZE_REWARD_VEC_CALL_INDEX: u8 = 5;
+/// Call index of `batch_all` in the utility pallet
+pub const UTILITY_BATCH_ALL_CALL_INDEX: u8 = 2;
+
+/// Parameters describing the runtime the calls are generated for
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuntimeParameters {
+	/// Index of the crowdloan rewards pallet in `construct_runtime!`
+	pub crowdloan_pallet_index: u8,
+	/// Index of the utility pallet in `construct_runtime!`
+	pub utility_pallet_index: u8,
+	/// `MaxInitContributors` of the runtime
+	pub max_init_contributors: u32,
+	/// `MinimumReward` of the runtime. Only used to report contributions that will be skipped
+	pub minimum_reward: u128,
+	/// Length in bytes of the native `AccountId` (eg 32 for `AccountId32`, 20 for `H160`)
+	pub native_account_length: usize,
+	/// Number of `initialize_reward_vec` calls wrapped in each `utility::batch_all`
+	pub calls_per_batch: u32,
+}
+
+impl RuntimeParameters {
+	fn validate(&self) -> Result<(), Error> {
+		if self.max_init_contributors == 0 {
+			return Err(Error::InvalidParameters(
+				"max_init_contributors must be bigger than 0",
+			));
+		}
+		if self.calls_per_batch == 0 {
+			return Err(Error::InvalidParameters(
+				"calls_per_batch must be bigger than 0",
+			));
+		}
+		if self.native_account_length == 0 {
+			return Err(Error::InvalidParameters(
+				"native_account_length must be bigger than 0",
+			));
+		}
+		Ok(())
+	}
+}
+
+/// A single crowdloan contribution, as it will be passed to `initialize_reward_vec`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Contribution {
+	/// The relay chain account that contributed
+	pub relay_account: [u8; 32],
+	/// The native account that will receive the rewards, if already known
+	pub native_account: Option<Vec<u8>>,
+	/// The amount of rewards this contribution is entitled to
+	pub reward: u128,
+}
+
+// Encodes as `(RelayChainAccountId, Option<AccountId>, Balance)`. The native account is kept as
+// raw bytes so it encodes like any fixed size account id, ie without a length prefix
+impl Encode for Contribution {
+	fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+		self.relay_account.encode_to(dest);
+		match &self.native_account {
+			Some(native_account) => {
+				dest.push_byte(1);
+				dest.write(native_account);
+			}
+			None => dest.push_byte(0),
+		}
+		self.reward.encode_to(dest);
+	}
+}
+
+/// Errors that can happen while generating the calls
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+	/// A line of the contribution file could not be parsed
+	InvalidLine { line: usize, reason: String },
+	/// The runtime parameters are not usable
+	InvalidParameters(&'static str),
+	/// The contribution file does not contain any contribution
+	NoContributions,
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::InvalidLine { line, reason } => write!(f, "line {}: {}", line, reason),
+			Error::InvalidParameters(reason) => write!(f, "invalid parameters: {}", reason),
+			Error::NoContributions => write!(f, "no contributions found"),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+/// A `utility::batch_all` of `initialize_reward_vec` calls
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Batch {
+	/// Position in the contribution list of the first contribution included in this batch
+	pub first_contribution: usize,
+	/// Number of contributions included in this batch
+	pub contributions: usize,
+	/// Sum of the rewards included in this batch
+	pub reward: u128,
+	/// SCALE-encoded call data of the batch
+	pub call_data: Vec<u8>,
+}
+
+/// Summary of the generated calls, meant to be reviewed alongside the proposal
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Summary {
+	/// Total number of contributions
+	pub contributions: usize,
+	/// Contributions that already have a native account
+	pub associated: usize,
+	/// Contributions that will need to be associated later on
+	pub unassociated: usize,
+	/// Sum of every reward, including the rows the pallet will skip
+	pub total_reward: u128,
+	/// Sum of the rewards the pallet will actually initialize. The pallet pot needs to match this
+	/// for `complete_initialization` to succeed
+	pub initialized_reward: u128,
+	/// Relay accounts that appear again after being initialized. The pallet will skip these rows
+	pub duplicated_relay_accounts: Vec<[u8; 32]>,
+	/// Relay accounts whose reward is below `MinimumReward`. The pallet will skip them
+	pub below_minimum_reward: Vec<[u8; 32]>,
+	/// blake2_256 of the SCALE encoding of every generated call, in order
+	pub checksum: [u8; 32],
+}
+
+/// Everything needed to initialize the pallet
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeneratedCalls {
+	/// The `utility::batch_all` calls, to be dispatched in order
+	pub batches: Vec<Batch>,
+	/// The `complete_initialization` call, to be dispatched after every batch
+	pub complete_initialization: Vec<u8>,
+	/// Summary of the generated calls
+	pub summary: Summary,
+}
+
+/// Parse a contribution file. See the crate docs for the expected format
+pub fn parse_contributions(
+	input: &str,
+	native_account_length: usize,
+) -> Result<Vec<Contribution>, Error> {
+	let mut contributions = Vec::new();
+	for (index, raw_line) in input.lines().enumerate() {
+		let line = raw_line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+		let invalid = |reason: String| Error::InvalidLine {
+			line: index + 1,
+			reason,
+		};
+
+		let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+		if fields.len() != 3 {
+			return Err(invalid(format!(
+				"expected 3 fields, found {}",
+				fields.len()
+			)));
+		}
+
+		let relay_account: [u8; 32] = decode_hex(fields[0])
+			.map_err(&invalid)?
+			.try_into()
+			.map_err(|_| invalid("relay account must be 32 bytes long".into()))?;
+
+		let native_account = if fields[1].is_empty() {
+			None
+		} else {
+			let native_account = decode_hex(fields[1]).map_err(&invalid)?;
+			if native_account.len() != native_account_length {
+				return Err(invalid(format!(
+					"native account must be {} bytes long",
+					native_account_length
+				)));
+			}
+			Some(native_account)
+		};
+
+		let reward = fields[2]
+			.parse::<u128>()
+			.map_err(|e| invalid(format!("invalid reward: {}", e)))?;
+
+		contributions.push(Contribution {
+			relay_account,
+			native_account,
+			reward,
+		});
+	}
+	Ok(contributions)
+}
+
+/// Generate the calls needed to initialize the pallet with the given contributions
+pub fn generate(
+	params: &RuntimeParameters,
+	contributions: &[Contribution],
+	lease_ending_block: u32,
+) -> Result<GeneratedCalls, Error> {
+	params.validate()?;
+	if contributions.is_empty() {
+		return Err(Error::NoContributions);
+	}
+
+	let rows_per_batch = params.max_init_contributors as usize * params.calls_per_batch as usize;
+	let mut batches = Vec::new();
+	for (index, batch_contributions) in contributions.chunks(rows_per_batch).enumerate() {
+		let calls: Vec<Vec<u8>> = batch_contributions
+			.chunks(params.max_init_contributors as usize)
+			.map(|rewards| encode_initialize_reward_vec(params, rewards))
+			.collect();
+		batches.push(Batch {
+			first_contribution: index * rows_per_batch,
+			contributions: batch_contributions.len(),
+			reward: batch_contributions
+				.iter()
+				.fold(0u128, |acc, c| acc.saturating_add(c.reward)),
+			call_data: encode_batch_all(params, &calls),
+		});
+	}
+
+	let complete_initialization = encode_complete_initialization(params, lease_ending_block);
+	let summary = summarize(params, contributions, &batches, &complete_initialization);
+
+	Ok(GeneratedCalls {
+		batches,
+		complete_initialization,
+		summary,
+	})
+}
+
+/// Call data of `initialize_reward_vec(rewards)`
+pub fn encode_initialize_reward_vec(
+	params: &RuntimeParameters,
+	rewards: &[Contribution],
+) -> Vec<u8> {
+	let mut call = vec![
+		params.crowdloan_pallet_index,
+		INITIALIZE_REWARD_VEC_CALL_INDEX,
+	];
+	rewards.encode_to(&mut call);
+	call
+}
+
+/// Call data of `complete_initialization(lease_ending_block)`
+pub fn encode_complete_initialization(
+	params: &RuntimeParameters,
+	lease_ending_block: u32,
+) -> Vec<u8> {
+	let mut call = vec![
+		params.crowdloan_pallet_index,
+		COMPLETE_INITIALIZATION_CALL_INDEX,
+	];
+	lease_ending_block.encode_to(&mut call);
+	call
+}
+
+/// Call data of `utility::batch_all(calls)`, where every call is already encoded
+pub fn encode_batch_all(params: &RuntimeParameters, calls: &[Vec<u8>]) -> Vec<u8> {
+	let mut call = vec![params.utility_pallet_index, UTILITY_BATCH_ALL_CALL_INDEX];
+	Compact(calls.len() as u32).encode_to(&mut call);
+	for inner in calls {
+		call.extend_from_slice(inner);
+	}
+	call
+}
+
+fn summarize(
+	params: &RuntimeParameters,
+	contributions: &[Contribution],
+	batches: &[Batch],
+	complete_initialization: &[u8],
+) -> Summary {
+	// Same checks, in the same order, as the pallet does for every row
+	let mut initialized = BTreeSet::new();
+	let mut initialized_reward = 0u128;
+	let mut duplicated_relay_accounts = Vec::new();
+	let mut below_minimum_reward = Vec::new();
+	for contribution in contributions {
+		if initialized.contains(&contribution.relay_account) {
+			duplicated_relay_accounts.push(contribution.relay_account);
+		} else if contribution.reward < params.minimum_reward {
+			below_minimum_reward.push(contribution.relay_account);
+		} else {
+			initialized.insert(contribution.relay_account);
+			initialized_reward = initialized_reward.saturating_add(contribution.reward);
+		}
+	}
+
+	let associated = contributions
+		.iter()
+		.filter(|c| c.native_account.is_some())
+		.count();
+
+	let calls: Vec<&[u8]> = batches
+		.iter()
+		.map(|batch| batch.call_data.as_slice())
+		.chain(std::iter::once(complete_initialization))
+		.collect();
+
+	Summary {
+		contributions: contributions.len(),
+		associated,
+		unassociated: contributions.len() - associated,
+		total_reward: contributions
+			.iter()
+			.fold(0u128, |acc, c| acc.saturating_add(c.reward)),
+		initialized_reward,
+		duplicated_relay_accounts,
+		below_minimum_reward,
+		checksum: blake2_256(&calls.encode()),
+	}
+}
+
+impl fmt::Display for GeneratedCalls {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (index, batch) in self.batches.iter().enumerate() {
+			writeln!(
+				f,
+				"# batch {}: contributions {}..{}, reward {}, blake2_256 0x{}",
+				index + 1,
+				batch.first_contribution,
+				batch.first_contribution + batch.contributions,
+				batch.reward,
+				hex::encode(blake2_256(&batch.call_data)),
+			)?;
+			writeln!(f, "0x{}", hex::encode(&batch.call_data))?;
+		}
+		writeln!(
+			f,
+			"# complete_initialization, blake2_256 0x{}",
+			hex::encode(blake2_256(&self.complete_initialization)),
+		)?;
+		writeln!(f, "0x{}", hex::encode(&self.complete_initialization))?;
+		write!(f, "{}", self.summary)
+	}
+}
+
+impl fmt::Display for Summary {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "# contributions: {}", self.contributions)?;
+		writeln!(f, "#   associated: {}", self.associated)?;
+		writeln!(f, "#   unassociated: {}", self.unassociated)?;
+		writeln!(f, "# total reward: {}", self.total_reward)?;
+		writeln!(
+			f,
+			"# initialized reward (pot to fund): {}",
+			self.initialized_reward
+		)?;
+		for relay_account in &self.duplicated_relay_accounts {
+			writeln!(
+				f,
+				"# warning: duplicated relay account 0x{}",
+				hex::encode(relay_account)
+			)?;
+		}
+		for relay_account in &self.below_minimum_reward {
+			writeln!(
+				f,
+				"# warning: reward below minimum for relay account 0x{}",
+				hex::encode(relay_account)
+			)?;
+		}
+		writeln!(f, "# checksum: 0x{}", hex::encode(self.checksum))
+	}
+}
+
+fn decode_hex(input: &str) -> Result<Vec<u8>, String> {
+	hex::decode(input.trim_start_matches("0x")).map_err(|e| format!("invalid hex: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn params() -> RuntimeParameters {
+		RuntimeParameters {
+			crowdloan_pallet_index: 20,
+			utility_pallet_index: 30,
+			max_init_contributors: 2,
+			minimum_reward: 10,
+			native_account_length: 20,
+			calls_per_batch: 2,
+		}
+	}
+
+	fn contribution(seed: u8, native: bool, reward: u128) -> Contribution {
+		Contribution {
+			relay_account: [seed; 32],
+			native_account: native.then(|| vec![seed; 20]),
+			reward,
+		}
+	}
+
+	#[test]
+	fn parsing_works() {
+		let input = format!(
+			"# relay,native,reward\n\n0x{},{},100\n{},,5\n",
+			hex::encode([1u8; 32]),
+			hex::encode([1u8; 20]),
+			hex::encode([2u8; 32]),
+		);
+		assert_eq!(
+			parse_contributions(&input, 20),
+			Ok(vec![contribution(1, true, 100), contribution(2, false, 5)])
+		);
+	}
+
+	#[test]
+	fn parsing_rejects_malformed_lines() {
+		let wrong_native = format!("{},{},100", hex::encode([1u8; 32]), hex::encode([1u8; 32]));
+		assert!(matches!(
+			parse_contributions(&wrong_native, 20),
+			Err(Error::InvalidLine { line: 1, .. })
+		));
+		let wrong_relay = format!("\n{},,100", hex::encode([1u8; 20]));
+		assert!(matches!(
+			parse_contributions(&wrong_relay, 20),
+			Err(Error::InvalidLine { line: 2, .. })
+		));
+		let wrong_reward = format!("{},,-1", hex::encode([1u8; 32]));
+		assert!(matches!(
+			parse_contributions(&wrong_reward, 20),
+			Err(Error::InvalidLine { line: 1, .. })
+		));
+	}
+
+	#[test]
+	fn contributions_encode_like_the_pallet_arguments() {
+		let rewards = vec![contribution(1, true, 100), contribution(2, false, 5)];
+		let expected: Vec<([u8; 32], Option<[u8; 20]>, u128)> =
+			vec![([1u8; 32], Some([1u8; 20]), 100), ([2u8; 32], None, 5)];
+
+		let mut call = vec![20, INITIALIZE_REWARD_VEC_CALL_INDEX];
+		call.append(&mut expected.encode());
+		assert_eq!(encode_initialize_reward_vec(&params(), &rewards), call);
+	}
+
+	#[test]
+	fn generate_splits_in_batches() {
+		let rewards: Vec<Contribution> =
+			(1..=5).map(|i| contribution(i, i % 2 == 0, 100)).collect();
+		let generated = generate(&params(), &rewards, 1000).unwrap();
+
+		// 2 rows per call and 2 calls per batch
+		assert_eq!(generated.batches.len(), 2);
+		assert_eq!(generated.batches[0].contributions, 4);
+		assert_eq!(generated.batches[0].reward, 400);
+		assert_eq!(generated.batches[1].first_contribution, 4);
+		assert_eq!(generated.batches[1].contributions, 1);
+
+		let first_calls = vec![
+			encode_initialize_reward_vec(&params(), &rewards[0..2]),
+			encode_initialize_reward_vec(&params(), &rewards[2..4]),
+		];
+		let mut expected = vec![30, UTILITY_BATCH_ALL_CALL_INDEX, 8];
+		for call in first_calls {
+			expected.extend(call);
+		}
+		assert_eq!(generated.batches[0].call_data, expected);
+
+		let mut complete = vec![20, COMPLETE_INITIALIZATION_CALL_INDEX];
+		complete.append(&mut 1000u32.encode());
+		assert_eq!(generated.complete_initialization, complete);
+
+		assert_eq!(generated.summary.contributions, 5);
+		assert_eq!(generated.summary.associated, 2);
+		assert_eq!(generated.summary.unassociated, 3);
+		assert_eq!(generated.summary.total_reward, 500);
+		assert_eq!(generated.summary.initialized_reward, 500);
+	}
+
+	#[test]
+	fn summary_reports_rows_the_pallet_will_skip() {
+		let rewards = vec![
+			contribution(1, true, 100),
+			contribution(2, false, 5),
+			contribution(1, false, 100),
+		];
+		let summary = generate(&params(), &rewards, 1000).unwrap().summary;
+		assert_eq!(summary.duplicated_relay_accounts, vec![[1u8; 32]]);
+		assert_eq!(summary.below_minimum_reward, vec![[2u8; 32]]);
+		assert_eq!(summary.total_reward, 205);
+		assert_eq!(summary.initialized_reward, 100);
+	}
+
+	#[test]
+	fn rows_after_a_skipped_one_are_not_duplicates() {
+		// The first row is skipped for its reward, so the pallet inserts the second one
+		let rewards = vec![contribution(1, true, 5), contribution(1, false, 100)];
+		let summary = generate(&params(), &rewards, 1000).unwrap().summary;
+		assert!(summary.duplicated_relay_accounts.is_empty());
+		assert_eq!(summary.below_minimum_reward, vec![[1u8; 32]]);
+		assert_eq!(summary.initialized_reward, 100);
+	}
+
+	#[test]
+	fn checksum_depends_on_every_call() {
+		let rewards = vec![contribution(1, true, 100)];
+		let first = generate(&params(), &rewards, 1000)
+			.unwrap()
+			.summary
+			.checksum;
+		let second = generate(&params(), &rewards, 1001)
+			.unwrap()
+			.summary
+			.checksum;
+		assert_ne!(first, second);
+	}
+
+	#[test]
+	fn generate_rejects_invalid_input() {
+		assert_eq!(generate(&params(), &[], 1000), Err(Error::NoContributions));
+		let mut params = params();
+		params.max_init_contributors = 0;
+		assert!(matches!(
+			generate(&params, &[contribution(1, true, 100)], 1000),
+			Err(Error::InvalidParameters(_))
+		));
+	}
+}
@@ -0,0 +1,102 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Command line interface of the batch generator.
+//!
+//! Prints the hex encoded call data of every batch and of the completion call, each of them
+//! preceded by a `#` line with its blake2_256 hash, followed by the summary report.
+
+use crowdloan_rewards_batch_generator::{generate, parse_contributions, RuntimeParameters};
+use std::collections::BTreeMap;
+
+const USAGE: &str = "Usage: crowdloan-batch-generator \
+	--contributions <FILE> \
+	--crowdloan-pallet-index <INDEX> \
+	--utility-pallet-index <INDEX> \
+	--max-init-contributors <NUMBER> \
+	--lease-ending-block <BLOCK> \
+	[--native-account-length <BYTES> (default 32)] \
+	[--minimum-reward <AMOUNT> (default 0)] \
+	[--calls-per-batch <NUMBER> (default 1)]";
+
+fn main() {
+	if let Err(e) = run() {
+		eprintln!("error: {}", e);
+		eprintln!("{}", USAGE);
+		std::process::exit(1);
+	}
+}
+
+fn run() -> Result<(), String> {
+	let args = parse_args(std::env::args().skip(1))?;
+
+	let params = RuntimeParameters {
+		crowdloan_pallet_index: required(&args, "crowdloan-pallet-index")?,
+		utility_pallet_index: required(&args, "utility-pallet-index")?,
+		max_init_contributors: required(&args, "max-init-contributors")?,
+		minimum_reward: optional(&args, "minimum-reward", 0)?,
+		native_account_length: optional(&args, "native-account-length", 32)?,
+		calls_per_batch: optional(&args, "calls-per-batch", 1)?,
+	};
+	let lease_ending_block: u32 = required(&args, "lease-ending-block")?;
+
+	let path = args
+		.get("contributions")
+		.ok_or_else(|| "missing --contributions".to_string())?;
+	let input =
+		std::fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path, e))?;
+
+	let contributions =
+		parse_contributions(&input, params.native_account_length).map_err(|e| e.to_string())?;
+	let generated =
+		generate(&params, &contributions, lease_ending_block).map_err(|e| e.to_string())?;
+
+	print!("{}", generated);
+	Ok(())
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<BTreeMap<String, String>, String> {
+	let mut parsed = BTreeMap::new();
+	let mut args = args;
+	while let Some(arg) = args.next() {
+		let key = arg
+			.strip_prefix("--")
+			.ok_or_else(|| format!("unexpected argument {}", arg))?;
+		let value = args
+			.next()
+			.ok_or_else(|| format!("missing value for --{}", key))?;
+		parsed.insert(key.to_string(), value);
+	}
+	Ok(parsed)
+}
+
+fn required<T: std::str::FromStr>(args: &BTreeMap<String, String>, key: &str) -> Result<T, String> {
+	let value = args.get(key).ok_or_else(|| format!("missing --{}", key))?;
+	value
+		.parse()
+		.map_err(|_| format!("invalid value for --{}: {}", key, value))
+}
+
+fn optional<T: std::str::FromStr>(
+	args: &BTreeMap<String, String>,
+	key: &str,
+	default: T,
+) -> Result<T, String> {
+	match args.get(key) {
+		Some(_) => required(args, key),
+		None => Ok(default),
+	}
+}
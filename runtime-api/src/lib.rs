@@ -0,0 +1,38 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the crowdloan rewards pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet_crowdloan_rewards::{InitializationOutcome, InitializationSimulation};
+use parity_scale_codec::Codec;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait CrowdloanRewardsApi<RelayChainAccountId, AccountId, Balance> where
+		RelayChainAccountId: Codec,
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Simulate initialize_reward_vec with the given rewards without modifying any storage, so
+		/// governance proposals can be verified before they are enacted.
+		fn simulate_initialize(
+			rewards: Vec<(RelayChainAccountId, Option<AccountId>, Balance)>,
+		) -> Result<InitializationSimulation<Balance>, DispatchError>;
+	}
+}
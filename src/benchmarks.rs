@@ -1,10 +1,15 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use crate::Config;
-use crate::{BalanceOf, Call, Pallet, WRAPPED_BYTES_POSTFIX, WRAPPED_BYTES_PREFIX};
+use crate::{
+	BalanceOf, Call, ContributorAccount, Pallet, PausableCall, PendingRewardAddressChange,
+	PendingRewardAddressChanges, RelayDelegation, CANCEL_CHANGE_BYTES, DELEGATION_BYTES,
+	WRAPPED_BYTES_POSTFIX, WRAPPED_BYTES_PREFIX,
+};
 use ed25519_dalek::Signer;
-use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
-use frame_support::traits::{Currency, Get, OnFinalize};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, BenchmarkError};
+use frame_support::traits::{Currency, EnsureOrigin, Get, OnFinalize};
+use frame_support::weights::Weight;
 use frame_system::RawOrigin;
 use parity_scale_codec::Encode;
 use sp_core::{
@@ -12,8 +17,8 @@ use sp_core::{
 	ed25519,
 };
 use sp_runtime::{
-	traits::{BlockNumberProvider, One},
-	MultiSignature,
+	traits::{BlockNumberProvider, One, Zero},
+	MultiSignature, Perbill,
 };
 use sp_std::vec;
 use sp_std::vec::Vec;
@@ -46,6 +51,16 @@ fn create_funded_user<T: Config>(
 	user
 }
 
+/// Create a relay chain account from a seed.
+fn create_relay_account<T: Config>(seed: u32) -> T::RelayChainAccountId {
+	let mut account: [u8; 32] = [0u8; 32];
+	let seed_as_slice = seed.to_be_bytes();
+	for j in 0..seed_as_slice.len() {
+		account[j] = seed_as_slice[j]
+	}
+	AccountId32::from(account).into()
+}
+
 /// Create contributors.
 fn create_contributors<T: Config>(
 	total_number: u32,
@@ -54,15 +69,10 @@ fn create_contributors<T: Config>(
 	let mut contribution_vec = Vec::new();
 	for i in 0..total_number {
 		let seed = SEED - seed_offset - i;
-		let mut account: [u8; 32] = [0u8; 32];
-		let seed_as_slice = seed.to_be_bytes();
-		for j in 0..seed_as_slice.len() {
-			account[j] = seed_as_slice[j]
-		}
-		let relay_chain_account: AccountId32 = account.into();
+		let relay_chain_account = create_relay_account::<T>(seed);
 		let user = create_funded_user::<T>("user", seed, 0u32.into());
 		let contribution: BalanceOf<T> = 100u32.into();
-		contribution_vec.push((relay_chain_account.into(), Some(user.clone()), contribution));
+		contribution_vec.push((relay_chain_account, Some(user.clone()), contribution));
 	}
 	contribution_vec
 }
@@ -79,18 +89,37 @@ fn insert_contributors<T: Config>(
 		sub_vec.push(contributors[i].clone());
 		// If we reached the batch size, we should insert them
 		if i as u32 % batch == batch - 1 || i == contributors.len() - 1 {
-			Pallet::<T>::initialize_reward_vec(RawOrigin::Root.into(), sub_vec.clone())?;
+			let origin = T::InitializationOrigin::try_successful_origin()
+				.map_err(|_| "Cannot create the initialization origin")?;
+			Pallet::<T>::initialize_reward_vec(origin, sub_vec.clone())?;
 			sub_vec.clear()
 		}
 	}
 	Ok(())
 }
 
+/// Give who a reward made of n contributions, each from a different relay account
+fn insert_relay_addresses<T: Config>(
+	who: &T::AccountId,
+	n: u32,
+	seed_offset: u32,
+) -> Result<(), &'static str> {
+	let contributors = (0..n)
+		.map(|i| {
+			let relay_account = create_relay_account::<T>(SEED - seed_offset - i);
+			(relay_account, Some(who.clone()), 100u32.into())
+		})
+		.collect();
+	insert_contributors::<T>(contributors)
+}
+
 /// Create a Contributor.
 fn close_initialization<T: Config>(
 	end_vesting_block: T::VestingBlockNumber,
 ) -> Result<(), &'static str> {
-	Pallet::<T>::complete_initialization(RawOrigin::Root.into(), end_vesting_block)?;
+	let origin = T::InitializationOrigin::try_successful_origin()
+		.map_err(|_| "Cannot create the initialization origin")?;
+	Pallet::<T>::complete_initialization(origin, end_vesting_block)?;
 	Ok(())
 }
 
@@ -142,7 +171,10 @@ benchmarks! {
 
 		let verifier = create_funded_user::<T>("user", SEED, 0u32.into());
 
-	}:  _(RawOrigin::Root, new_contributors)
+		let origin = T::InitializationOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+	}:  _<T::RuntimeOrigin>(origin, new_contributors)
 	verify {
 		assert!(Pallet::<T>::accounts_payable(&verifier).is_some());
 	}
@@ -161,7 +193,10 @@ benchmarks! {
 		T::VestingBlockProvider::set_block_number(1u32.into());
 		Pallet::<T>::on_finalize(T::BlockNumber::one());
 
-	}:  _(RawOrigin::Root, 10u32.into())
+		let origin = T::InitializationOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+	}:  _<T::RuntimeOrigin>(origin, 10u32.into())
 	verify {
 	  assert!(Pallet::<T>::initialized());
 	}
@@ -193,25 +228,31 @@ benchmarks! {
 		T::VestingBlockProvider::set_block_number(4u32.into());
 	}:  _(RawOrigin::Signed(caller.clone()))
 	verify {
-	  assert_eq!(Pallet::<T>::accounts_payable(&caller).unwrap().total_reward, (100u32.into()));
+		assert_eq!(Pallet::<T>::accounts_payable(&caller).unwrap().total_reward, (100u32.into()));
+		// The claim went through the free claim budget
+		if !T::FreeClaimsPerPeriod::get().is_zero() {
+			assert_eq!(Pallet::<T>::free_claims_used(&caller).1, 1);
+		}
 	}
 
 	update_reward_address {
+		// The relay addresses of both rewards, which get merged
+		let y in 2..max_batch_contributors::<T>();
+
 		// Fund pallet account
-		let total_pot = 100u32;
+		let total_pot = 100u32*y;
 		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
 
 		// The user that will make the call
 		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
 
-		let relay_account: T::RelayChainAccountId = AccountId32::from([1u8;32]).into();
-		// We verified there is no dependency of the number of contributors already inserted in update_reward_address
-		// Create 1 contributor
-		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
-			vec![(relay_account.clone(), Some(caller.clone()), total_pot.into())];
+		// The new user, which already has a reward
+		let new_user = create_funded_user::<T>("user", SEED+1, 0u32.into());
 
-		// Insert them
-		insert_contributors::<T>(contributors)?;
+		// We verified there is no dependency of the number of contributors already inserted in update_reward_address
+		insert_relay_addresses::<T>(&caller, y/2, 0)?;
+		insert_relay_addresses::<T>(&new_user, y - y/2, y)?;
+		Pallet::<T>::approve_merge(RawOrigin::Signed(new_user.clone()).into(), caller.clone())?;
 
 		// Close initialization
 		close_initialization::<T>(10u32.into())?;
@@ -224,13 +265,14 @@ benchmarks! {
 		// Let's advance the relay so that the vested  amount get transferred
 		T::VestingBlockProvider::set_block_number(4u32.into());
 
-		// The new user
-		let new_user = create_funded_user::<T>("user", SEED+1, 0u32.into());
-
-	}:  _(RawOrigin::Signed(caller.clone()), new_user.clone())
+	}:  _(RawOrigin::Signed(caller.clone()), new_user.clone(), y)
 	verify {
-		assert_eq!(Pallet::<T>::accounts_payable(&new_user).unwrap().total_reward, (100u32.into()));
-		assert!(Pallet::<T>::claimed_relay_chain_ids(&relay_account).is_some());
+		if T::RewardAddressChangeDelay::get().is_zero() {
+			assert_eq!(Pallet::<T>::accounts_payable(&new_user).unwrap().total_reward, total_pot.into());
+		} else {
+			assert!(Pallet::<T>::pending_reward_address_changes(&caller).is_some());
+		}
+		assert!(Pallet::<T>::claimed_relay_chain_ids(&create_relay_account::<T>(SEED)).is_some());
 	}
 
 	associate_native_identity {
@@ -265,7 +307,106 @@ benchmarks! {
 		T::VestingBlockProvider::set_block_number(1u32.into());
 		Pallet::<T>::on_finalize(T::BlockNumber::one());
 
-	}:  _(RawOrigin::Signed(caller.clone()), caller.clone(), relay_account.into(), signature)
+	}:  _(RawOrigin::Signed(caller.clone()), caller.clone(), relay_account.into(), signature, None)
+	verify {
+		assert_eq!(Pallet::<T>::accounts_payable(&caller).unwrap().total_reward, (100u32.into()));
+		// The initial payment went through the free claim budget
+		if !T::FreeClaimsPerPeriod::get().is_zero() {
+			assert_eq!(Pallet::<T>::free_claims_used(&caller).1, 1);
+		}
+	}
+
+	associate_native_identity_with_delegate {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The caller that will associate the account
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// Construct payload
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut T::SignatureNetworkIdentifier::get().to_vec());
+		payload.append(&mut caller.clone().encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+
+		// The delegate signs the association
+		let (delegate, signature) = create_sig::<T>(SEED-1, payload);
+		let delegation = RelayDelegation {
+			delegate: delegate.into(),
+			expires_at: 100u32.into(),
+		};
+
+		// The relay account signs the delegation
+		let mut delegation_payload = WRAPPED_BYTES_PREFIX.to_vec();
+		delegation_payload.append(&mut T::SignatureNetworkIdentifier::get().to_vec());
+		delegation_payload.append(&mut DELEGATION_BYTES.to_vec());
+		delegation_payload.append(&mut delegation.delegate.encode());
+		delegation_payload.append(&mut delegation.expires_at.encode());
+		delegation_payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+		let (relay_account, delegation_signature) = create_sig::<T>(SEED, delegation_payload);
+
+		// Create 1 contributor
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+		vec![(relay_account.clone().into(), None, total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Clonse initialization
+		close_initialization::<T>(10u32.into())?;
+
+		// First inherent
+		T::VestingBlockProvider::set_block_number(1u32.into());
+		Pallet::<T>::on_finalize(T::BlockNumber::one());
+
+	}:  _(RawOrigin::Signed(caller.clone()), caller.clone(), relay_account.into(), delegation, delegation_signature, signature, None)
+	verify {
+		assert_eq!(Pallet::<T>::accounts_payable(&caller).unwrap().total_reward, (100u32.into()));
+	}
+
+	associate_native_identity_with_multisig {
+		// Every signatory signs, so the weight depends on their number
+		let x in 1..T::MaxMultisigSignatories::get();
+
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The caller that will associate the account
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// Construct payload
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut T::SignatureNetworkIdentifier::get().to_vec());
+		payload.append(&mut caller.clone().encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+
+		// Create N sigs for N signatories
+		let mut signatories: Vec<T::RelayChainAccountId> = Vec::new();
+		let mut proofs: Vec<(T::RelayChainAccountId, MultiSignature)> = Vec::new();
+		for i in 0..x {
+			let (signatory, signature) = create_sig::<T>(SEED-i, payload.clone());
+			signatories.push(signatory.clone().into());
+			proofs.push((signatory.into(), signature));
+		}
+		let relay_account = Pallet::<T>::multisig_account_id(&signatories, x as u16);
+
+		// Create 1 contributor
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+		vec![(relay_account.clone(), None, total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Clonse initialization
+		close_initialization::<T>(10u32.into())?;
+
+		// First inherent
+		T::VestingBlockProvider::set_block_number(1u32.into());
+		Pallet::<T>::on_finalize(T::BlockNumber::one());
+
+	}:  _(RawOrigin::Signed(caller.clone()), caller.clone(), relay_account, signatories, x as u16, proofs, None)
 	verify {
 		assert_eq!(Pallet::<T>::accounts_payable(&caller).unwrap().total_reward, (100u32.into()));
 	}
@@ -276,9 +417,12 @@ benchmarks! {
 		// We need to parameterize this value
 		// We leave this as the max batch length
 		let x in 1..max_batch_contributors::<T>();
+		// The relay addresses of the reward it gets merged into. The witness of the call also
+		// counts the x of the moved reward
+		let y in 0..max_batch_contributors::<T>();
 
 		// Fund pallet account
-		let total_pot = 100u32*x;
+		let total_pot = 100u32*(x+y);
 		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
 
 		// The first reward account that will associate the account
@@ -311,6 +455,13 @@ benchmarks! {
 
 		// Insert them
 		insert_contributors::<T>(contributors.clone())?;
+		if y > 0 {
+			insert_relay_addresses::<T>(&second_reward_account, y, 0)?;
+			Pallet::<T>::approve_merge(
+				RawOrigin::Signed(second_reward_account.clone()).into(),
+				first_reward_account.clone(),
+			)?;
+		}
 
 		// Clonse initialization
 		close_initialization::<T>(10u32.into())?;
@@ -319,14 +470,746 @@ benchmarks! {
 		T::VestingBlockProvider::set_block_number(1u32.into());
 		Pallet::<T>::on_finalize(T::BlockNumber::one());
 
-	}:  _(RawOrigin::Signed(first_reward_account.clone()), second_reward_account.clone(), first_reward_account.clone(), proofs)
+		// The new reward account submits the change, which is its consent if it needs one
+	}:  _(RawOrigin::Signed(second_reward_account.clone()), second_reward_account.clone(), first_reward_account.clone(), proofs, x+y, None)
 	verify {
 		assert!(Pallet::<T>::accounts_payable(&second_reward_account).is_some());
-		assert_eq!(Pallet::<T>::accounts_payable(&second_reward_account).unwrap().total_reward, (100u32*x).into());
+		assert_eq!(Pallet::<T>::accounts_payable(&second_reward_account).unwrap().total_reward, total_pot.into());
 		assert!(Pallet::<T>::accounts_payable(&first_reward_account).is_none());
 
 	}
 
+	adjust_reward {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The contributor whose reward will be adjusted
+		let contributor: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(contributor.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Close initialization
+		close_initialization::<T>(10u32.into())?;
+
+		// The increase is taken from the funding account
+		fund_specific_account::<T>(T::RewardFundingAccount::get(), 1000u32.into());
+
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+	}:  _<T::RuntimeOrigin>(origin, ContributorAccount::Native(contributor.clone()), 200u32.into())
+	verify {
+		assert_eq!(Pallet::<T>::accounts_payable(&contributor).unwrap().total_reward, (200u32.into()));
+	}
+
+	add_late_contributor {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			create_contributors::<T>(1, 0);
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Close initialization
+		close_initialization::<T>(10u32.into())?;
+
+		// The contributor that was left out
+		let late_contributor: T::AccountId = create_funded_user::<T>("user", SEED+1, 0u32.into());
+		let relay_account = create_relay_account::<T>(SEED+1);
+
+		// The reward is taken from the funding account
+		fund_specific_account::<T>(T::RewardFundingAccount::get(), 1000u32.into());
+
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+	}:  _<T::RuntimeOrigin>(origin, relay_account.clone(), Some(late_contributor.clone()), 100u32.into())
+	verify {
+		assert_eq!(Pallet::<T>::accounts_payable(&late_contributor).unwrap().total_reward, (100u32.into()));
+		assert!(Pallet::<T>::claimed_relay_chain_ids(&relay_account).is_some());
+	}
+
+	remove_contributor {
+		// The weight will depend on the number of relay accounts of the contributor
+		let x in 1..max_batch_contributors::<T>();
+
+		// Fund pallet account
+		let total_pot = 100u32*x;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The contributor that will be removed
+		let contributor: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// Create x contributions
+		// All of them map to the same account
+		let mut contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> = Vec::new();
+		for i in 0..x {
+			contributors.push((create_relay_account::<T>(SEED-i), Some(contributor.clone()), 100u32.into()));
+		}
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Close initialization
+		close_initialization::<T>(10u32.into())?;
+
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+	}:  _<T::RuntimeOrigin>(origin, ContributorAccount::Native(contributor.clone()), x)
+	verify {
+		assert!(Pallet::<T>::accounts_payable(&contributor).is_none());
+	}
+
+	pause {
+		let origin = T::PauseOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}:  _<T::RuntimeOrigin>(origin, PausableCall::Claim)
+	verify {
+		assert!(Pallet::<T>::paused(PausableCall::Claim).is_some());
+	}
+
+	unpause {
+		let origin = T::PauseOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		Pallet::<T>::pause(origin.clone(), PausableCall::Claim)?;
+	}:  _<T::RuntimeOrigin>(origin, PausableCall::Claim)
+	verify {
+		assert!(Pallet::<T>::paused(PausableCall::Claim).is_none());
+	}
+
+	claim_for {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// The contributor that will get paid
+		let beneficiary: T::AccountId = create_funded_user::<T>("user", SEED+1, 100u32.into());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(beneficiary.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Close initialization
+		close_initialization::<T>(10u32.into())?;
+
+		// First inherent
+		T::VestingBlockProvider::set_block_number(1u32.into());
+		Pallet::<T>::on_finalize(T::BlockNumber::one());
+
+		// Create 4th relay block, by now the user should have vested some amount
+		T::VestingBlockProvider::set_block_number(4u32.into());
+	}:  _(RawOrigin::Signed(caller.clone()), beneficiary.clone())
+	verify {
+		assert_eq!(Pallet::<T>::accounts_payable(&beneficiary).unwrap().total_reward, (100u32.into()));
+	}
+
+	claim_many {
+		let x in 1..T::MaxClaimBatch::get();
+
+		// Fund pallet account
+		let total_pot = 100u32*x;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("caller", SEED, 100u32.into());
+
+		// Create x contributors, all of them associated
+		let contributors = create_contributors::<T>(x, 0);
+		let accounts: Vec<T::AccountId> = contributors
+			.iter()
+			.filter_map(|(_, native, _)| native.clone())
+			.collect();
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Close initialization
+		close_initialization::<T>(10u32.into())?;
+
+		// First inherent
+		T::VestingBlockProvider::set_block_number(1u32.into());
+		Pallet::<T>::on_finalize(T::BlockNumber::one());
+
+		// Create 4th relay block, by now every user should have vested some amount
+		T::VestingBlockProvider::set_block_number(4u32.into());
+	}:  _(RawOrigin::Signed(caller), accounts.clone())
+	verify {
+		for account in accounts {
+			assert!(Pallet::<T>::accounts_payable(&account).unwrap().claimed_reward > 0u32.into());
+		}
+	}
+
+	auto_payout {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// A single associated contributor
+		let contributors = create_contributors::<T>(1, 0);
+		let user = contributors[0].1.clone().unwrap();
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Close initialization
+		close_initialization::<T>(10u32.into())?;
+
+		// First inherent
+		T::VestingBlockProvider::set_block_number(1u32.into());
+		Pallet::<T>::on_finalize(T::BlockNumber::one());
+
+		// Create 4th relay block, by now the user should have vested some amount
+		T::VestingBlockProvider::set_block_number(4u32.into());
+	}: {
+		Pallet::<T>::do_auto_payouts(1, Weight::MAX);
+	}
+	verify {
+		assert!(Pallet::<T>::auto_payout_cursor().is_some());
+		assert_eq!(Pallet::<T>::accounts_payable(&user).unwrap().total_reward, (100u32.into()));
+	}
+
+	claim_to {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// The account getting the payout
+		let dest: T::AccountId = create_funded_user::<T>("user", SEED+1, 0u32.into());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(caller.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Close initialization
+		close_initialization::<T>(10u32.into())?;
+
+		// First inherent
+		T::VestingBlockProvider::set_block_number(1u32.into());
+		Pallet::<T>::on_finalize(T::BlockNumber::one());
+
+		// Create 4th relay block, by now the user should have vested some amount
+		T::VestingBlockProvider::set_block_number(4u32.into());
+	}:  _(RawOrigin::Signed(caller.clone()), dest)
+	verify {
+		assert_eq!(Pallet::<T>::accounts_payable(&caller).unwrap().total_reward, (100u32.into()));
+	}
+
+	claim_amount {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(caller.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Close initialization
+		close_initialization::<T>(10u32.into())?;
+
+		// First inherent
+		T::VestingBlockProvider::set_block_number(1u32.into());
+		Pallet::<T>::on_finalize(T::BlockNumber::one());
+
+		// Create 4th relay block, by now the user should have vested some amount
+		T::VestingBlockProvider::set_block_number(4u32.into());
+		let claimed_before = Pallet::<T>::accounts_payable(&caller).unwrap().claimed_reward;
+	}:  _(RawOrigin::Signed(caller.clone()), 1u32.into())
+	verify {
+		assert_eq!(
+			Pallet::<T>::accounts_payable(&caller).unwrap().claimed_reward,
+			claimed_before + 1u32.into()
+		);
+	}
+
+	stake_unvested_rewards {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(caller.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Close initialization
+		close_initialization::<T>(10u32.into())?;
+
+		// First inherent
+		T::VestingBlockProvider::set_block_number(1u32.into());
+		Pallet::<T>::on_finalize(T::BlockNumber::one());
+	}:  _(RawOrigin::Signed(caller.clone()), 10u32.into())
+	verify {
+		assert_eq!(Pallet::<T>::staked_unvested_rewards(&caller), 10u32.into());
+	}
+
+	unbond_staked_rewards {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(caller.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Close initialization
+		close_initialization::<T>(10u32.into())?;
+
+		// First inherent
+		T::VestingBlockProvider::set_block_number(1u32.into());
+		Pallet::<T>::on_finalize(T::BlockNumber::one());
+
+		Pallet::<T>::stake_unvested_rewards(RawOrigin::Signed(caller.clone()).into(), 20u32.into())?;
+
+		// Create the last relay block, by now the whole reward is vested
+		T::VestingBlockProvider::set_block_number(10u32.into());
+	}:  _(RawOrigin::Signed(caller.clone()), 10u32.into())
+	verify {
+		assert_eq!(Pallet::<T>::staked_unvested_rewards(&caller), 10u32.into());
+	}
+
+	approve_merge {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// The account whose reward would be merged
+		let source: T::AccountId = create_funded_user::<T>("user", SEED+1, 0u32.into());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(caller.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+	}:  _(RawOrigin::Signed(caller.clone()), source.clone())
+	verify {
+		assert!(Pallet::<T>::merge_approvals(&caller, &source).is_some());
+	}
+
+	cancel_merge_approval {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// The account whose reward would be merged
+		let source: T::AccountId = create_funded_user::<T>("user", SEED+1, 0u32.into());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(caller.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		Pallet::<T>::approve_merge(RawOrigin::Signed(caller.clone()).into(), source.clone())?;
+	}:  _(RawOrigin::Signed(caller.clone()), source.clone())
+	verify {
+		assert!(Pallet::<T>::merge_approvals(&caller, &source).is_none());
+	}
+
+	split_reward {
+		let x in 2..T::MaxRewardSplits::get();
+		// The relay addresses held by every slice
+		let y in 1..max_batch_contributors::<T>();
+
+		// Rewards can not be split or transferred while address changes are delayed
+		if !T::RewardAddressChangeDelay::get().is_zero() {
+			return Err(BenchmarkError::Weightless);
+		}
+
+		// Fund pallet account
+		let total_pot = 100u32*y;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		insert_relay_addresses::<T>(&caller, y, 0)?;
+
+		// Split in x equal parts, the last one taking what is left
+		let share = Perbill::from_rational(1u32, x);
+		let mut destinations: Vec<(T::AccountId, Perbill)> = Vec::new();
+		for i in 0..x-1 {
+			destinations.push((create_funded_user::<T>("split", i, 0u32.into()), share));
+		}
+		let last = create_funded_user::<T>("split", x, 0u32.into());
+		destinations.push((last.clone(), Perbill::one() - share * (x-1)));
+	}:  _(RawOrigin::Signed(caller.clone()), destinations, y)
+	verify {
+		assert!(Pallet::<T>::accounts_payable(&caller).is_none());
+		assert!(Pallet::<T>::accounts_payable(&last).is_some());
+	}
+
+	set_transfers_enabled {
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}:  _<T::RuntimeOrigin>(origin, true)
+	verify {
+		assert!(Pallet::<T>::transfers_enabled());
+	}
+
+	transfer_position {
+		// Rewards can not be split or transferred while address changes are delayed
+		if !T::RewardAddressChangeDelay::get().is_zero() {
+			return Err(BenchmarkError::Weightless);
+		}
+
+		// Fund pallet account
+		let total_pot = 200u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// Worst case is merging into an existing reward
+		let dest: T::AccountId = create_funded_user::<T>("user", SEED+1, 100u32.into());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> = vec![
+			(AccountId32::from([1u8;32]).into(), Some(caller.clone()), 100u32.into()),
+			(AccountId32::from([2u8;32]).into(), Some(dest.clone()), 100u32.into()),
+		];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		Pallet::<T>::set_transfers_enabled(origin, true)?;
+		Pallet::<T>::approve_merge(RawOrigin::Signed(dest.clone()).into(), caller.clone())?;
+	}:  _(RawOrigin::Signed(caller.clone()), dest.clone(), Perbill::from_percent(50))
+	verify {
+		assert_eq!(Pallet::<T>::accounts_payable(&dest).unwrap().total_reward, (150u32.into()));
+	}
+
+	cancel_reward_address_change {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(caller.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Queue the change directly, as the delay might be zero
+		let new_user = create_funded_user::<T>("user", SEED+1, 0u32.into());
+		PendingRewardAddressChanges::<T>::insert(&caller, PendingRewardAddressChange {
+			new_account: new_user,
+			executable_at: frame_system::Pallet::<T>::block_number(),
+		});
+	}:  _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Pallet::<T>::pending_reward_address_changes(&caller).is_none());
+	}
+
+	cancel_reward_address_change_with_relay_key {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The reward account with the change queued
+		let owner: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("caller", SEED, 100u32.into());
+
+		let new_user = create_funded_user::<T>("user", SEED+1, 0u32.into());
+		let executable_at = frame_system::Pallet::<T>::block_number();
+
+		// Construct payload
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut T::SignatureNetworkIdentifier::get().to_vec());
+		payload.append(&mut CANCEL_CHANGE_BYTES.to_vec());
+		payload.append(&mut owner.clone().encode());
+		payload.append(&mut new_user.clone().encode());
+		payload.append(&mut executable_at.encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+		let (relay_account, signature) = create_sig::<T>(SEED, payload);
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(relay_account.clone().into(), Some(owner.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		// Queue the change directly, as the delay might be zero
+		PendingRewardAddressChanges::<T>::insert(&owner, PendingRewardAddressChange {
+			new_account: new_user,
+			executable_at,
+		});
+	}:  _(RawOrigin::Signed(caller.clone()), owner.clone(), relay_account.into(), signature)
+	verify {
+		assert!(Pallet::<T>::pending_reward_address_changes(&owner).is_none());
+	}
+
+	execute_pending_change {
+		// The relay addresses of both rewards, which get merged
+		let y in 2..max_batch_contributors::<T>();
+
+		// Fund pallet account
+		let total_pot = 100u32*y;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The reward account with the change queued
+		let owner: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("caller", SEED, 100u32.into());
+
+		// The new user, which already has a reward
+		let new_user = create_funded_user::<T>("user", SEED+1, 0u32.into());
+
+		insert_relay_addresses::<T>(&owner, y/2, 0)?;
+		insert_relay_addresses::<T>(&new_user, y - y/2, y)?;
+		Pallet::<T>::approve_merge(RawOrigin::Signed(new_user.clone()).into(), owner.clone())?;
+
+		// Queue the change directly, as the delay might be zero
+		PendingRewardAddressChanges::<T>::insert(&owner, PendingRewardAddressChange {
+			new_account: new_user.clone(),
+			executable_at: frame_system::Pallet::<T>::block_number(),
+		});
+	}:  _(RawOrigin::Signed(caller.clone()), owner.clone(), y)
+	verify {
+		assert!(Pallet::<T>::accounts_payable(&owner).is_none());
+		assert_eq!(Pallet::<T>::accounts_payable(&new_user).unwrap().total_reward, total_pot.into());
+		assert!(Pallet::<T>::pending_reward_address_changes(&owner).is_none());
+	}
+
+	request_recovery {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The account whose key was lost
+		let lost_account: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// The user that will make the call and receive the reward
+		let caller: T::AccountId = create_funded_user::<T>("caller", SEED, T::RecoveryDeposit::get());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(lost_account.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+	}:  _(RawOrigin::Signed(caller.clone()), lost_account.clone(), caller.clone(), Default::default())
+	verify {
+		assert!(Pallet::<T>::recovery_requests(&lost_account).is_some());
+	}
+
+	approve_recovery {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The account whose key was lost
+		let lost_account: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// The user that requests the recovery and receives the reward
+		let caller: T::AccountId = create_funded_user::<T>("caller", SEED, T::RecoveryDeposit::get());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(lost_account.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		Pallet::<T>::request_recovery(
+			RawOrigin::Signed(caller.clone()).into(),
+			lost_account.clone(),
+			caller.clone(),
+			Default::default(),
+		)?;
+
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}:  _<T::RuntimeOrigin>(origin, lost_account.clone())
+	verify {
+		assert!(Pallet::<T>::recovery_requests(&lost_account).unwrap().executable_at.is_some());
+	}
+
+	reject_recovery {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The account whose key was lost
+		let lost_account: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// The user that requests the recovery and receives the reward
+		let caller: T::AccountId = create_funded_user::<T>("caller", SEED, T::RecoveryDeposit::get());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(lost_account.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		Pallet::<T>::request_recovery(
+			RawOrigin::Signed(caller.clone()).into(),
+			lost_account.clone(),
+			caller.clone(),
+			Default::default(),
+		)?;
+
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}:  _<T::RuntimeOrigin>(origin, lost_account.clone())
+	verify {
+		assert!(Pallet::<T>::recovery_requests(&lost_account).is_none());
+	}
+
+	cancel_recovery {
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The account whose key was lost
+		let lost_account: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// The user that requests the recovery and receives the reward
+		let caller: T::AccountId = create_funded_user::<T>("caller", SEED, T::RecoveryDeposit::get());
+
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(AccountId32::from([1u8;32]).into(), Some(lost_account.clone()), total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		Pallet::<T>::request_recovery(
+			RawOrigin::Signed(caller.clone()).into(),
+			lost_account.clone(),
+			caller.clone(),
+			Default::default(),
+		)?;
+	}:  _(RawOrigin::Signed(lost_account.clone()), lost_account.clone())
+	verify {
+		assert!(Pallet::<T>::recovery_requests(&lost_account).is_none());
+	}
+
+	finalize_recovery {
+		// The relay addresses of both rewards, which get merged
+		let y in 2..max_batch_contributors::<T>();
+
+		// Fund pallet account
+		let total_pot = 100u32*y;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The account whose key was lost
+		let lost_account: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// The user that requests the recovery and receives the reward, which already has one
+		let caller: T::AccountId = create_funded_user::<T>("caller", SEED, T::RecoveryDeposit::get());
+
+		insert_relay_addresses::<T>(&lost_account, y/2, 0)?;
+		insert_relay_addresses::<T>(&caller, y - y/2, y)?;
+		Pallet::<T>::approve_merge(RawOrigin::Signed(caller.clone()).into(), lost_account.clone())?;
+
+		Pallet::<T>::request_recovery(
+			RawOrigin::Signed(caller.clone()).into(),
+			lost_account.clone(),
+			caller.clone(),
+			Default::default(),
+		)?;
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		Pallet::<T>::approve_recovery(origin, lost_account.clone())?;
+
+		// Let the delay go by
+		let executable_at = Pallet::<T>::recovery_requests(&lost_account)
+			.and_then(|request| request.executable_at)
+			.ok_or("The recovery should be approved")?;
+		frame_system::Pallet::<T>::set_block_number(executable_at);
+	}:  _(RawOrigin::Signed(caller.clone()), lost_account.clone(), y)
+	verify {
+		assert!(Pallet::<T>::accounts_payable(&lost_account).is_none());
+		assert_eq!(Pallet::<T>::accounts_payable(&caller).unwrap().total_reward, total_pot.into());
+	}
+
+	approve_association {
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The relay account whose contribution would be associated
+		let relay_account: T::RelayChainAccountId = AccountId32::from([1u8;32]).into();
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(relay_account.clone(), None, total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+	}:  _(RawOrigin::Signed(caller.clone()), relay_account.clone())
+	verify {
+		assert!(Pallet::<T>::association_approvals(&caller, &relay_account).is_some());
+	}
+
+	cancel_association_approval {
+		// The user that will make the call
+		let caller: T::AccountId = create_funded_user::<T>("user", SEED, 100u32.into());
+
+		// Fund pallet account
+		let total_pot = 100u32;
+		fund_specific_account::<T>(Pallet::<T>::account_id(), total_pot.into());
+
+		// The relay account whose contribution would be associated
+		let relay_account: T::RelayChainAccountId = AccountId32::from([1u8;32]).into();
+		let contributors: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)> =
+			vec![(relay_account.clone(), None, total_pot.into())];
+
+		// Insert them
+		insert_contributors::<T>(contributors)?;
+
+		Pallet::<T>::approve_association(
+			RawOrigin::Signed(caller.clone()).into(),
+			relay_account.clone(),
+		)?;
+	}:  _(RawOrigin::Signed(caller.clone()), relay_account.clone())
+	verify {
+		assert!(Pallet::<T>::association_approvals(&caller, &relay_account).is_none());
+	}
 }
 #[cfg(test)]
 mod tests {
@@ -35,8 +35,9 @@
 //!
 //! The current payout mechanism requires contributors to claim their payouts. Because they are
 //! paying the transaction fees for this themselves, they can do it as often as every block, or
-//! wait and claim the entire thing once it is fully vested. We could consider auto payouts if we
-//! want.
+//! wait and claim the entire thing once it is fully vested. Runtimes can also opt into auto payouts
+//! by setting `MaxAutoPayoutsPerBlock`, in which case `on_idle` walks the payable accounts and pays
+//! whatever is vested using the leftover block weight.
 //!
 //! ## Sourcing Contribution Information
 //!
@@ -44,7 +45,8 @@
 //!
 //! * **Through the initialize_reward_vec extrinsic*
 //!
-//! The simplest way is to call the initialize_reward_vec through a democracy proposal/sudo call.
+//! The simplest way is to call the initialize_reward_vec through a democracy proposal/sudo call,
+//! or any other origin configured as InitializationOrigin.
 //! This makes sense in a scenario where the crowdloan took place entirely offchain.
 //! This extrinsic initializes the associated and unassociated stoerage with the provided data
 //!
@@ -64,32 +66,47 @@ use frame_support::pallet;
 pub use pallet::*;
 #[cfg(any(test, feature = "runtime-benchmarks"))]
 mod benchmarks;
+pub mod migrations;
 #[cfg(test)]
 pub(crate) mod mock;
 #[cfg(test)]
 mod tests;
+pub mod traits;
 pub mod weights;
 
 #[pallet]
 pub mod pallet {
 
+	use crate::traits::{OnRewardClaimed, RewardStaking};
 	use crate::weights::WeightInfo;
 	use frame_support::traits::WithdrawReasons;
 	use frame_support::{
 		pallet_prelude::*,
-		traits::{Currency, ExistenceRequirement::AllowDeath},
+		storage::{with_storage_layer, with_transaction},
+		traits::{
+			Currency,
+			ExistenceRequirement::{AllowDeath, KeepAlive},
+			ReservableCurrency,
+		},
 		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
 	use sp_core::crypto::AccountId32;
+	use sp_io::hashing::blake2_256;
 	use sp_runtime::traits::{
-		AccountIdConversion, AtLeast32BitUnsigned, BlockNumberProvider, Saturating, Verify,
+		AccountIdConversion, AtLeast32BitUnsigned, BlockNumberProvider, IdentifyAccount,
+		Saturating, TrailingZeroInput, Verify, Zero,
 	};
-	use sp_runtime::{MultiSignature, Perbill};
+	use sp_runtime::{MultiSignature, PerThing, Perbill, TransactionOutcome};
 	use sp_std::collections::btree_map::BTreeMap;
 	use sp_std::vec;
 	use sp_std::vec::Vec;
+
+	/// The current storage version
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	// The crowdloan rewards pallet
 	pub struct Pallet<T>(PhantomData<T>);
@@ -99,6 +116,14 @@ pub mod pallet {
 	// The wrapper around which the reward changing message needs to be wrapped
 	pub const WRAPPED_BYTES_PREFIX: &[u8] = b"<Bytes>";
 	pub const WRAPPED_BYTES_POSTFIX: &[u8] = b"</Bytes>";
+	// Same prefix pallet_multisig uses to derive the multisig account ids
+	pub const MULTISIG_ACCOUNT_PREFIX: &[u8; 16] = b"modlpy/utilisuba";
+	// Tells the delegation statements signed by relay accounts apart from the rest
+	pub const DELEGATION_BYTES: &[u8] = b"delegate";
+	// Tells the signatures cancelling a pending reward address change apart from the rest
+	pub const CANCEL_CHANGE_BYTES: &[u8] = b"cancel";
+	// Tells the signatures of reward accounts consenting to receive a reward apart from the rest
+	pub const CONSENT_BYTES: &[u8] = b"consent";
 
 	/// Configuration trait of this pallet.
 	#[pallet::config]
@@ -113,14 +138,68 @@ pub mod pallet {
 		// Max number of contributors that can be inserted at once in initialize_reward_vec
 		#[pallet::constant]
 		type MaxInitContributors: Get<u32>;
+		/// Max number of accounts that can be paid at once in claim_many
+		#[pallet::constant]
+		type MaxClaimBatch: Get<u32>;
+		/// Max number of signatories of the relay multisigs that can associate a contribution
+		#[pallet::constant]
+		type MaxMultisigSignatories: Get<u32>;
+		/// Max number of accounts a reward can be split into with split_reward
+		#[pallet::constant]
+		type MaxRewardSplits: Get<u32>;
+		/// Max number of payable accounts visited by on_idle in a single block.
+		/// Zero disables auto payouts
+		#[pallet::constant]
+		type MaxAutoPayoutsPerBlock: Get<u32>;
+		/// Vested amounts below this are left for a later auto payout or a manual claim
+		#[pallet::constant]
+		type MinimumAutoPayout: Get<BalanceOf<Self>>;
+		/// Number of successful claims and associations per account that are free of fees in
+		/// every FreeClaimPeriod. Zero makes every claim pay fees
+		#[pallet::constant]
+		type FreeClaimsPerPeriod: Get<u32>;
+		/// Length in blocks of the periods in which FreeClaimsPerPeriod applies.
+		/// Zero means a single period that never ends
+		#[pallet::constant]
+		type FreeClaimPeriod: Get<BlockNumberFor<Self>>;
 		/// The minimum contribution to which rewards will be paid.
 		type MinimumReward: Get<BalanceOf<Self>>;
+		/// Blocks a change made with update_reward_address waits before it can be executed, during
+		/// which the owner or any contributing relay key can cancel it. Zero applies changes
+		/// immediately
+		#[pallet::constant]
+		type RewardAddressChangeDelay: Get<BlockNumberFor<Self>>;
+		/// Deposit reserved from whoever requests a recovery. It is returned once the recovery is
+		/// finalized or withdrawn, and slashed if it is rejected or vetoed by the lost account
+		#[pallet::constant]
+		type RecoveryDeposit: Get<BalanceOf<Self>>;
+		/// Blocks between AdminOrigin approving a recovery and the recovery being finalized, during
+		/// which the owner of the lost account can still veto it
+		#[pallet::constant]
+		type RecoveryDelay: Get<BlockNumberFor<Self>>;
 		/// A fraction representing the percentage of proofs
 		/// that need to be presented to change a reward address through the relay keys
 		#[pallet::constant]
 		type RewardAddressRelayVoteThreshold: Get<Perbill>;
+		/// How the votes of the relay accounts are counted against RewardAddressRelayVoteThreshold
+		#[pallet::constant]
+		type RewardAddressRelayVoteMode: Get<RelayVoteMode>;
+		/// Whether associating a contribution, or changing it to an account without a reward, also
+		/// needs the consent of the reward account. It consents by submitting the call itself, by
+		/// a consent_proof signature or, for associations, through approve_association
+		#[pallet::constant]
+		type RequireNativeConsent: Get<bool>;
+		/// The signature reward accounts consent with, usually MultiSignature
+		type NativeSignature: Verify<Signer = Self::NativeSigner> + Parameter;
+		/// The signer of NativeSignature, usually MultiSigner
+		type NativeSigner: IdentifyAccount<AccountId = Self::AccountId>;
 		/// The currency in which the rewards will be paid (probably the parachain native currency)
-		type RewardCurrency: Currency<Self::AccountId>;
+		type RewardCurrency: ReservableCurrency<Self::AccountId>;
+		/// The account that funds reward increases and late contributors once the initialization
+		/// is complete, and that gets back the rewards that are reduced or removed. The pallet
+		/// never issues or burns funds for these amendments
+		#[pallet::constant]
+		type RewardFundingAccount: Get<Self::AccountId>;
 		/// The AccountId type contributors used on the relay chain.
 		type RelayChainAccountId: Parameter
 			//TODO these AccountId32 bounds feel a little extraneous. I wonder if we can remove them.
@@ -128,6 +207,20 @@ pub mod pallet {
 			+ From<AccountId32>
 			+ Ord;
 
+		/// The origin that is allowed to initialize the reward vec and complete the initialization
+		type InitializationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The origin that is allowed to amend rewards once the initialization is complete.
+		///
+		/// It is kept apart from InitializationOrigin because initializing is a one-off setup that
+		/// can be handed to a dedicated account, while amending, recovering or enabling transfers
+		/// changes the rewards of existing contributors and usually needs a stronger origin. Both
+		/// can be set to the same origin
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The origin that is allowed to pause and unpause calls in case of emergency
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
 		// The origin that is allowed to change the reward address with relay signatures
 		type RewardAddressChangeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
@@ -146,6 +239,20 @@ pub mod pallet {
 		/// either the relay chain or sovereign chain block number.
 		type VestingBlockProvider: BlockNumberProvider<BlockNumber = Self::VestingBlockNumber>;
 
+		/// Describes where claim_and_stake should forward the claimed rewards
+		type RewardClaimTarget: Parameter;
+
+		/// Forwards the rewards claimed through claim_and_stake into their target
+		type OnRewardClaimed: OnRewardClaimed<
+			Self::AccountId,
+			BalanceOf<Self>,
+			Self::RewardClaimTarget,
+		>;
+
+		/// Bonds unvested rewards on behalf of contributors.
+		/// Use () to disable staking unvested rewards
+		type RewardStaking: RewardStaking<Self::AccountId, BalanceOf<Self>>;
+
 		type WeightInfo: WeightInfo;
 	}
 
@@ -161,7 +268,111 @@ pub mod pallet {
 	pub struct RewardInfo<T: Config> {
 		pub total_reward: BalanceOf<T>,
 		pub claimed_reward: BalanceOf<T>,
-		pub contributed_relay_addresses: Vec<T::RelayChainAccountId>,
+		/// Relay accounts that contributed, together with the reward each of them was initialized
+		/// with. The reward is unknown for entries that merged several contributions before
+		/// storage version 1, as their split was never stored
+		pub contributed_relay_addresses: Vec<(T::RelayChainAccountId, Option<BalanceOf<T>>)>,
+	}
+
+	/// Identifies a reward entry, either by the native account it is associated with or, when it
+	/// has not been associated yet, by the relay account that contributed
+	#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub enum ContributorAccount<RelayChainAccountId, AccountId> {
+		/// A contribution that has not been associated with a native account yet
+		Relay(RelayChainAccountId),
+		/// A contribution that is associated with a native account
+		Native(AccountId),
+	}
+
+	/// Calls that can be paused by PauseOrigin
+	#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub enum PausableCall {
+		Claim,
+		AssociateNativeIdentity,
+		ChangeAssociationWithRelayKeys,
+		UpdateRewardAddress,
+	}
+
+	/// How the votes to change a reward address through the relay keys are counted
+	#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub enum RelayVoteMode {
+		/// Every relay account that contributed has one vote
+		PerAccount,
+		/// Every relay account votes with the reward it contributed
+		ContributionWeighted,
+	}
+
+	/// How a reward address was changed
+	#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub enum RewardAddressChangeMechanism<RelayChainAccountId> {
+		/// The owner of the reward called update_reward_address
+		SelfUpdate,
+		/// Relay accounts that contributed voted with their signatures
+		RelayKeyVote { voters: Vec<RelayChainAccountId> },
+		/// A recovery approved by AdminOrigin was finalized
+		Recovery,
+	}
+
+	/// Statement signed by a relay account allowing delegate to associate its contribution
+	#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub struct RelayDelegation<RelayChainAccountId, VestingBlockNumber> {
+		/// The relay account whose signature is accepted instead
+		pub delegate: RelayChainAccountId,
+		/// The vesting block from which the delegation is no longer valid
+		pub expires_at: VestingBlockNumber,
+	}
+
+	/// A reward address change queued by update_reward_address
+	#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub struct PendingRewardAddressChange<AccountId, BlockNumber> {
+		/// The account that will receive the reward
+		pub new_account: AccountId,
+		/// The block from which execute_pending_change can apply the change
+		pub executable_at: BlockNumber,
+	}
+
+	/// A request to move the reward of an account whose native key was lost
+	#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub struct RecoveryRequest<AccountId, Balance, Hash, BlockNumber> {
+		/// The account that requested the recovery and holds the deposit
+		pub requester: AccountId,
+		/// The account that will receive the reward
+		pub new_account: AccountId,
+		/// Hash of the off-chain evidence reviewed by AdminOrigin
+		pub evidence_hash: Hash,
+		/// The reserved deposit
+		pub deposit: Balance,
+		/// The block from which the recovery can be finalized, once approved
+		pub executable_at: Option<BlockNumber>,
+	}
+
+	/// What initialize_reward_vec does (or would do) with a single contribution
+	#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub enum InitializationOutcome {
+		/// A new reward entry is created
+		Inserted,
+		/// The reward is added to the entry of an already rewarded native account
+		MergedIntoExistingAccount,
+		/// The relay account was already initialized, so the contribution is skipped
+		SkippedAlreadyInitialized,
+		/// The contribution is below MinimumReward, so it is skipped
+		SkippedNotEnoughContribution,
+		/// The batch goes beyond the funds of the pot, so it would be rejected entirely. Every row
+		/// of such a batch gets this outcome
+		WouldExceedPot,
+	}
+
+	/// Result of simulating initialize_reward_vec
+	#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub struct InitializationSimulation<Balance> {
+		/// The outcome of each of the provided rows, in order
+		pub outcomes: Vec<InitializationOutcome>,
+		/// Total initialized amount after the batch
+		pub initialized_reward_amount: Balance,
+		/// Total number of contributors after the batch
+		pub total_contributors: u32,
+		/// Funds left in the pot after the initial payments of the batch
+		pub pot: Balance,
 	}
 
 	// This hook is in charge of initializing the vesting height at the first block of the parachain
@@ -173,6 +384,10 @@ pub mod pallet {
 				<InitVestingBlock<T>>::put(T::VestingBlockProvider::current_block_number());
 			}
 		}
+
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::do_auto_payouts(T::MaxAutoPayoutsPerBlock::get(), remaining_weight)
+		}
 	}
 
 	#[pallet::call]
@@ -182,6 +397,8 @@ pub mod pallet {
 		/// The caller needs to provide the unassociated relay account and a proof to succeed
 		/// with the association
 		/// The proof is nothing but a signature over the reward_address using the relay keys
+		/// consent_proof is only needed when RequireNativeConsent is set and the caller is not
+		/// reward_account, see ensure_native_consent
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::associate_native_identity())]
 		pub fn associate_native_identity(
@@ -189,9 +406,11 @@ pub mod pallet {
 			reward_account: T::AccountId,
 			relay_account: T::RelayChainAccountId,
 			proof: MultiSignature,
+			consent_proof: Option<T::NativeSignature>,
 		) -> DispatchResultWithPostInfo {
 			// Check that the origin is the one able to asociate the reward addrss
-			T::RewardAddressChangeOrigin::ensure_origin(origin)?;
+			T::RewardAddressChangeOrigin::ensure_origin(origin.clone())?;
+			Self::ensure_not_paused(PausableCall::AssociateNativeIdentity)?;
 
 			// Check the proof:
 			// 1. Is signed by an actual unassociated contributor
@@ -200,70 +419,144 @@ pub mod pallet {
 			// claimer key
 
 			// The less costly checks will go first
-
-			// The relay account should be unassociated
-			let mut reward_info = UnassociatedContributions::<T>::get(&relay_account)
-				.ok_or(Error::<T>::NoAssociatedClaim)?;
-
-			// We ensure the relay chain id wast not yet associated to avoid multi-claiming
-			// We dont need this right now, as it will always be true if the above check is true
-			ensure!(
-				ClaimedRelayChainIds::<T>::get(&relay_account).is_none(),
-				Error::<T>::AlreadyAssociated
-			);
-
-			// For now I prefer that we dont support providing an existing account here
-			ensure!(
-				AccountsPayable::<T>::get(&reward_account).is_none(),
-				Error::<T>::AlreadyAssociated
-			);
-
-			// b"<Bytes>" "SignatureNetworkIdentifier" + "new_account" + b"</Bytes>"
-			let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
-			payload.append(&mut T::SignatureNetworkIdentifier::get().to_vec());
-			payload.append(&mut reward_account.encode());
-			payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+			let reward_info = Self::associable_reward_info(&reward_account, &relay_account)?;
 
 			// Check the signature
 			Self::verify_signatures(
 				vec![(relay_account.clone(), proof)],
 				reward_info.clone(),
-				payload,
+				Self::association_payload(&reward_account),
 			)?;
 
-			// Make the first payment
-			let first_payment = T::InitializationPayment::get() * reward_info.total_reward;
+			Self::ensure_native_consent(origin, &reward_account, &relay_account, consent_proof)?;
+			let first_payment =
+				Self::associate(reward_account.clone(), relay_account, reward_info)?;
 
-			T::RewardCurrency::transfer(
-				&PALLET_ID.into_account_truncating(),
-				&reward_account,
-				first_payment,
-				AllowDeath,
-			)?;
+			if !first_payment.is_zero() && Self::use_free_claim(&reward_account) {
+				return Ok(Pays::No.into());
+			}
+			Ok(Default::default())
+		}
 
-			Self::deposit_event(Event::InitialPaymentMade(
-				reward_account.clone(),
-				first_payment,
-			));
+		/// Associate a native rewards_destination identity with a crowdloan contribution, with the
+		/// signature of a delegate of the contributing relay account, eg one of its relay proxies.
+		///
+		/// delegation_proof is the signature of relay_account over the delegation, and proof the
+		/// signature of the delegate over the reward_address. The delegation expires at the given
+		/// vesting block. consent_proof is the same as in associate_native_identity
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::associate_native_identity_with_delegate())]
+		pub fn associate_native_identity_with_delegate(
+			origin: OriginFor<T>,
+			reward_account: T::AccountId,
+			relay_account: T::RelayChainAccountId,
+			delegation: RelayDelegation<T::RelayChainAccountId, T::VestingBlockNumber>,
+			delegation_proof: MultiSignature,
+			proof: MultiSignature,
+			consent_proof: Option<T::NativeSignature>,
+		) -> DispatchResultWithPostInfo {
+			// Check that the origin is the one able to asociate the reward addrss
+			T::RewardAddressChangeOrigin::ensure_origin(origin.clone())?;
+			Self::ensure_not_paused(PausableCall::AssociateNativeIdentity)?;
+			ensure!(
+				T::VestingBlockProvider::current_block_number() < delegation.expires_at,
+				Error::<T>::DelegationExpired
+			);
 
-			reward_info.claimed_reward = first_payment;
+			let reward_info = Self::associable_reward_info(&reward_account, &relay_account)?;
 
-			// Insert on payable
-			AccountsPayable::<T>::insert(&reward_account, &reward_info);
+			// b"<Bytes>" + "SignatureNetworkIdentifier" + b"delegate" + "delegate" + "expires_at"
+			// + b"</Bytes>"
+			let mut delegation_payload = WRAPPED_BYTES_PREFIX.to_vec();
+			delegation_payload.append(&mut T::SignatureNetworkIdentifier::get().to_vec());
+			delegation_payload.append(&mut DELEGATION_BYTES.to_vec());
+			delegation_payload.append(&mut delegation.delegate.encode());
+			delegation_payload.append(&mut delegation.expires_at.encode());
+			delegation_payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+			ensure!(
+				delegation_proof
+					.verify(delegation_payload.as_slice(), &relay_account.clone().into()),
+				Error::<T>::InvalidDelegationSignature
+			);
+			ensure!(
+				proof.verify(
+					Self::association_payload(&reward_account).as_slice(),
+					&delegation.delegate.into()
+				),
+				Error::<T>::InvalidClaimSignature
+			);
 
-			// Remove from unassociated
-			<UnassociatedContributions<T>>::remove(&relay_account);
+			Self::ensure_native_consent(origin, &reward_account, &relay_account, consent_proof)?;
+			let first_payment =
+				Self::associate(reward_account.clone(), relay_account, reward_info)?;
 
-			// Insert in mapping
-			ClaimedRelayChainIds::<T>::insert(&relay_account, ());
+			if !first_payment.is_zero() && Self::use_free_claim(&reward_account) {
+				return Ok(Pays::No.into());
+			}
+			Ok(Default::default())
+		}
 
-			// Emit Event
-			Self::deposit_event(Event::NativeIdentityAssociated(
-				relay_account,
-				reward_account,
-				reward_info.total_reward,
-			));
+		/// Associate a native rewards_destination identity with a crowdloan contribution made from
+		/// a relay multisig account.
+		///
+		/// The multisig account is derived from the signatories and the threshold as in
+		/// pallet_multisig, and at least threshold signatories need to sign the reward_address.
+		/// consent_proof is the same as in associate_native_identity
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::associate_native_identity_with_multisig(proofs.len() as u32))]
+		pub fn associate_native_identity_with_multisig(
+			origin: OriginFor<T>,
+			reward_account: T::AccountId,
+			relay_account: T::RelayChainAccountId,
+			signatories: Vec<T::RelayChainAccountId>,
+			threshold: u16,
+			proofs: Vec<(T::RelayChainAccountId, MultiSignature)>,
+			consent_proof: Option<T::NativeSignature>,
+		) -> DispatchResultWithPostInfo {
+			// Check that the origin is the one able to asociate the reward addrss
+			T::RewardAddressChangeOrigin::ensure_origin(origin.clone())?;
+			Self::ensure_not_paused(PausableCall::AssociateNativeIdentity)?;
+			ensure!(
+				signatories.len() as u32 <= T::MaxMultisigSignatories::get()
+					&& threshold > 0 && threshold as usize <= signatories.len(),
+				Error::<T>::InvalidMultisig
+			);
+			ensure!(
+				Self::multisig_account_id(&signatories, threshold) == relay_account,
+				Error::<T>::InvalidMultisig
+			);
 
+			let reward_info = Self::associable_reward_info(&reward_account, &relay_account)?;
+
+			// Check the signatures of the signatories
+			let payload = Self::association_payload(&reward_account);
+			let mut signed: BTreeMap<T::RelayChainAccountId, ()> = BTreeMap::new();
+			for (signatory, signature) in proofs {
+				if signed.contains_key(&signatory) {
+					continue;
+				}
+				ensure!(
+					signatories.contains(&signatory),
+					Error::<T>::NonContributedAddressProvided
+				);
+				ensure!(
+					signature.verify(payload.as_slice(), &signatory.clone().into()),
+					Error::<T>::InvalidClaimSignature
+				);
+				signed.insert(signatory, ());
+			}
+			ensure!(
+				signed.len() >= threshold as usize,
+				Error::<T>::InsufficientNumberOfValidProofs
+			);
+
+			Self::ensure_native_consent(origin, &reward_account, &relay_account, consent_proof)?;
+			let first_payment =
+				Self::associate(reward_account.clone(), relay_account, reward_info)?;
+
+			if !first_payment.is_zero() && Self::use_free_claim(&reward_account) {
+				return Ok(Pays::No.into());
+			}
 			Ok(Default::default())
 		}
 
@@ -271,70 +564,1548 @@ pub mod pallet {
 		///
 		/// The number of valid proofs needs to be bigger than 'RewardAddressRelayVoteThreshold'
 		/// The account to be changed needs to be submitted as 'previous_account'
+		/// If reward_account already has a reward, both are merged as long as reward_account
+		/// approved it with approve_merge
+		/// Otherwise reward_account needs to consent when RequireNativeConsent is set, by being the
+		/// caller or with a consent_proof signature over previous_account
+		/// relay_addresses needs to be at least the number of relay addresses of both rewards, as
+		/// the weight depends on it
 
 		/// Origin must be RewardAddressChangeOrigin
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::change_association_with_relay_keys(proofs.len() as u32))]
+		#[pallet::weight(T::WeightInfo::change_association_with_relay_keys(
+			proofs.len() as u32,
+			*relay_addresses,
+		))]
 		pub fn change_association_with_relay_keys(
 			origin: OriginFor<T>,
 			reward_account: T::AccountId,
 			previous_account: T::AccountId,
 			proofs: Vec<(T::RelayChainAccountId, MultiSignature)>,
+			relay_addresses: u32,
+			consent_proof: Option<T::NativeSignature>,
 		) -> DispatchResultWithPostInfo {
 			// Check that the origin is the one able to change the reward addrss
-			T::RewardAddressChangeOrigin::ensure_origin(origin)?;
+			T::RewardAddressChangeOrigin::ensure_origin(origin.clone())?;
+			Self::ensure_not_paused(PausableCall::ChangeAssociationWithRelayKeys)?;
+
+			// An existing account needs to have approved the merge, a new one to consent
+			Self::ensure_can_receive_reward(&reward_account, &previous_account)?;
+			if !AccountsPayable::<T>::contains_key(&reward_account) {
+				Self::ensure_signed_consent(
+					origin,
+					&reward_account,
+					previous_account.encode(),
+					consent_proof,
+				)?;
+			}
 
-			// For now I prefer that we dont support providing an existing account here
+			// To avoid replay attacks, we make sure the payload contains the previous address too
+			// I am assuming no rational user will go back to a previously changed reward address
+			// b"<Bytes>" + "SignatureNetworkIdentifier" + new_account" + "previous_account" + b"</Bytes>"
+			let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+			payload.append(&mut T::SignatureNetworkIdentifier::get().to_vec());
+			payload.append(&mut reward_account.encode());
+			payload.append(&mut previous_account.encode());
+			payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+
+			// Get the reward info for the account to be changed
+			let reward_info = AccountsPayable::<T>::get(&previous_account)
+				.ok_or(Error::<T>::NoAssociatedClaim)?;
 			ensure!(
-				AccountsPayable::<T>::get(&reward_account).is_none(),
-				Error::<T>::AlreadyAssociated
+				!StakedUnvestedRewards::<T>::contains_key(&previous_account),
+				Error::<T>::RewardsStaked
+			);
+
+			let relay_addresses =
+				Self::moved_relay_addresses(&reward_info, &reward_account, relay_addresses)?;
+
+			let voters = Self::verify_signatures(proofs, reward_info.clone(), payload)?;
+			let verified_signatures = voters.len() as u32;
+
+			Self::move_reward_info(&previous_account, &reward_account, reward_info)?;
+
+			// Emit Event
+			Self::deposit_event(Event::RewardAddressUpdated {
+				previous_account,
+				new_account: reward_account,
+				mechanism: RewardAddressChangeMechanism::RelayKeyVote { voters },
+			});
+
+			Ok(Some(T::WeightInfo::change_association_with_relay_keys(
+				verified_signatures,
+				relay_addresses,
+			))
+			.into())
+		}
+
+		/// Collect whatever portion of your reward are currently vested.
+		///
+		/// Free of fees as long as something gets paid and the free claims of the period are not
+		/// exhausted
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::claim())]
+		pub fn claim(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let payee = ensure_signed(origin)?;
+			let amount = Self::do_claim(payee.clone())?;
+			if !amount.is_zero() && Self::use_free_claim(&payee) {
+				return Ok(Pays::No.into());
+			}
+			Ok(Default::default())
+		}
+
+		/// Pay whatever portion of the reward of beneficiary is currently vested.
+		///
+		/// Anyone can trigger the payout, but the funds always go to the reward account of the
+		/// beneficiary, never to the caller
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::claim_for())]
+		pub fn claim_for(
+			origin: OriginFor<T>,
+			beneficiary: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::do_claim(beneficiary)?;
+			Ok(Default::default())
+		}
+
+		/// Pay whatever portion of the rewards of several accounts is currently vested.
+		///
+		/// Accounts with nothing to claim are skipped rather than failing the whole call, so this
+		/// can be used to push payouts for many contributors at vesting milestones
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::claim_many(accounts.len() as u32))]
+		pub fn claim_many(
+			origin: OriginFor<T>,
+			accounts: Vec<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(
+				accounts.len() as u32 <= T::MaxClaimBatch::get(),
+				Error::<T>::TooManyClaims
+			);
+			Self::ensure_not_paused(PausableCall::Claim)?;
+			let initialized = <Initialized<T>>::get();
+			ensure!(initialized, Error::<T>::RewardVecNotFullyInitializedYet);
+
+			let mut paid_accounts = 0u32;
+			let mut skipped_accounts = 0u64;
+			let mut total_paid: BalanceOf<T> = 0u32.into();
+			for account in accounts {
+				let info = match AccountsPayable::<T>::get(&account) {
+					Some(info) if info.claimed_reward < info.total_reward => info,
+					_ => {
+						skipped_accounts += 1;
+						continue;
+					}
+				};
+				let payable_amount = Self::vested_unclaimed_reward(&info);
+				if payable_amount.is_zero() {
+					skipped_accounts += 1;
+					continue;
+				}
+				Self::pay_reward(account, info, payable_amount)?;
+				paid_accounts += 1;
+				total_paid = total_paid.saturating_add(payable_amount);
+			}
+
+			Self::deposit_event(Event::RewardsBatchPaid {
+				accounts_paid: paid_accounts,
+				total_paid,
+			});
+
+			// Skipped accounts only cost reading their reward info
+			Ok(Some(
+				T::WeightInfo::claim_many(paid_accounts)
+					.saturating_add(T::DbWeight::get().reads(skipped_accounts)),
+			)
+			.into())
+		}
+
+		/// Collect whatever portion of your reward are currently vested, paying it to dest.
+		///
+		/// Unlike update_reward_address, the reward stays associated with the caller
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::claim_to())]
+		pub fn claim_to(origin: OriginFor<T>, dest: T::AccountId) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let info = Self::claimable_reward_info(&owner)?;
+			let payable_amount = Self::vested_unclaimed_reward(&info);
+			let info = Self::transfer_reward(&owner, &dest, info, payable_amount)?;
+
+			Self::deposit_event(Event::RewardsPaidTo {
+				account: owner,
+				dest,
+				amount: payable_amount,
+				total_claimed: info.claimed_reward,
+				remaining_unvested: info.total_reward.saturating_sub(Self::vested_reward(&info)),
+				vesting_block: T::VestingBlockProvider::current_block_number(),
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Collect only part of the portion of your reward that is currently vested.
+		///
+		/// The rest stays in the pot and can be claimed later
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::claim_amount())]
+		pub fn claim_amount(
+			origin: OriginFor<T>,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let payee = ensure_signed(origin)?;
+			let info = Self::claimable_reward_info(&payee)?;
+			ensure!(
+				amount <= Self::vested_unclaimed_reward(&info),
+				Error::<T>::ClaimAmountExceedsVestedReward
+			);
+			Self::pay_reward(payee, info, amount)?;
+
+			Ok(Default::default())
+		}
+
+		/// Collect whatever portion of your reward are currently vested and forward it into target,
+		/// e.g. a staking or liquidity position, through the OnRewardClaimed hook.
+		///
+		/// The claim is reverted if the hook fails
+		#[pallet::call_index(15)]
+		#[pallet::weight(
+			T::WeightInfo::claim().saturating_add(T::OnRewardClaimed::weight(target))
+		)]
+		pub fn claim_and_stake(
+			origin: OriginFor<T>,
+			target: T::RewardClaimTarget,
+		) -> DispatchResultWithPostInfo {
+			let payee = ensure_signed(origin)?;
+			let amount = Self::do_claim(payee.clone())?;
+			T::OnRewardClaimed::on_reward_claimed(&payee, amount, target.clone())?;
+
+			Self::deposit_event(Event::RewardsForwarded {
+				account: payee,
+				amount,
+				target,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Stake part of your unclaimed reward before it vests.
+		///
+		/// The amount is paid out and immediately bonded through RewardStaking, counting as
+		/// claimed. It can only be unbonded as it vests, with unbond_staked_rewards
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::stake_unvested_rewards())]
+		pub fn stake_unvested_rewards(
+			origin: OriginFor<T>,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut info = Self::claimable_reward_info(&who)?;
+			ensure!(
+				amount <= info.total_reward.saturating_sub(info.claimed_reward),
+				Error::<T>::StakeAmountExceedsUnclaimedReward
+			);
+
+			info.claimed_reward = info.claimed_reward.saturating_add(amount);
+			AccountsPayable::<T>::insert(&who, &info);
+			StakedUnvestedRewards::<T>::mutate(&who, |staked| {
+				*staked = staked.saturating_add(amount)
+			});
+
+			T::RewardCurrency::transfer(
+				&PALLET_ID.into_account_truncating(),
+				&who,
+				amount,
+				AllowDeath,
+			)?;
+			T::RewardStaking::bond(&who, amount)?;
+
+			Self::deposit_event(Event::UnvestedRewardsStaked {
+				account: who,
+				amount,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Unbond staked rewards that have vested since they were staked
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::unbond_staked_rewards())]
+		pub fn unbond_staked_rewards(
+			origin: OriginFor<T>,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let info = AccountsPayable::<T>::get(&who).ok_or(Error::<T>::NoAssociatedClaim)?;
+			let staked = StakedUnvestedRewards::<T>::get(&who);
+
+			// Whatever was claimed beyond the staked amount is already liquid
+			let liquid_claimed = info.claimed_reward.saturating_sub(staked);
+			let unbondable = Self::vested_reward(&info)
+				.saturating_sub(liquid_claimed)
+				.min(staked);
+			ensure!(
+				amount <= unbondable,
+				Error::<T>::UnbondAmountExceedsVestedStake
 			);
 
-			// To avoid replay attacks, we make sure the payload contains the previous address too
-			// I am assuming no rational user will go back to a previously changed reward address
-			// b"<Bytes>" + "SignatureNetworkIdentifier" + new_account" + "previous_account" + b"</Bytes>"
+			let remaining = staked.saturating_sub(amount);
+			if remaining.is_zero() {
+				StakedUnvestedRewards::<T>::remove(&who);
+			} else {
+				StakedUnvestedRewards::<T>::insert(&who, remaining);
+			}
+			T::RewardStaking::unbond(&who, amount)?;
+
+			Self::deposit_event(Event::StakedRewardsUnbonded {
+				account: who,
+				amount,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Consent to the reward of source being merged into the reward of the caller, through
+		/// update_reward_address or change_association_with_relay_keys
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::approve_merge())]
+		pub fn approve_merge(
+			origin: OriginFor<T>,
+			source: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let target = ensure_signed(origin)?;
+			ensure!(target != source, Error::<T>::CannotMergeIntoItself);
+			ensure!(
+				AccountsPayable::<T>::contains_key(&target),
+				Error::<T>::NoAssociatedClaim
+			);
+
+			MergeApprovals::<T>::insert(&target, &source, ());
+
+			Self::deposit_event(Event::MergeApproved { target, source });
+
+			Ok(Default::default())
+		}
+
+		/// Withdraw a consent given with approve_merge
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::cancel_merge_approval())]
+		pub fn cancel_merge_approval(
+			origin: OriginFor<T>,
+			source: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let target = ensure_signed(origin)?;
+			ensure!(
+				MergeApprovals::<T>::take(&target, &source).is_some(),
+				Error::<T>::MergeNotApproved
+			);
+
+			Self::deposit_event(Event::MergeApprovalCancelled { target, source });
+
+			Ok(Default::default())
+		}
+
+		/// Split the reward of the caller into new rewards for each of the destinations, with the
+		/// given share of the total and claimed reward.
+		///
+		/// The shares need to add up to 100%, and every slice needs to end up with part of the
+		/// reward. Every slice holds the contributed relay addresses of the original reward, each
+		/// with the same share of what it contributed. Rounding leftovers go to the last
+		/// destination.
+		///
+		/// Splits can not be queued, so they are rejected while RewardAddressChangeDelay is set.
+		/// relay_addresses needs to be at least the number of relay addresses of the reward, as
+		/// the weight depends on it
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::split_reward(destinations.len() as u32, *relay_addresses))]
+		pub fn split_reward(
+			origin: OriginFor<T>,
+			destinations: Vec<(T::AccountId, Perbill)>,
+			relay_addresses: u32,
+		) -> DispatchResultWithPostInfo {
+			let source = ensure_signed(origin)?;
+			Self::ensure_not_paused(PausableCall::UpdateRewardAddress)?;
+			Self::ensure_reward_can_move_now(&source)?;
+			ensure!(
+				destinations.len() as u32 <= T::MaxRewardSplits::get(),
+				Error::<T>::TooManySplits
+			);
+			let info = AccountsPayable::<T>::get(&source).ok_or(Error::<T>::NoAssociatedClaim)?;
+			ensure!(
+				!StakedUnvestedRewards::<T>::contains_key(&source),
+				Error::<T>::RewardsStaked
+			);
+			ensure!(
+				info.contributed_relay_addresses.len() as u32 <= relay_addresses,
+				Error::<T>::TooFewRelayAddresses
+			);
+
+			let shares: u64 = destinations
+				.iter()
+				.map(|(_, share)| share.deconstruct() as u64)
+				.sum();
+			ensure!(
+				shares == Perbill::one().deconstruct() as u64,
+				Error::<T>::InvalidSplit
+			);
+			let mut seen: BTreeMap<T::AccountId, ()> = BTreeMap::new();
+			for (destination, _) in &destinations {
+				// The source is removed before inserting the slices, so it can keep one
+				ensure!(
+					destination == &source || !AccountsPayable::<T>::contains_key(destination),
+					Error::<T>::AlreadyAssociated
+				);
+				ensure!(
+					seen.insert(destination.clone(), ()).is_none(),
+					Error::<T>::InvalidSplit
+				);
+			}
+
+			// Claimed and unclaimed parts are split separately, so no slice can end up with more
+			// claimed than total reward
+			let unclaimed = info.total_reward.saturating_sub(info.claimed_reward);
+			let mut claimed_left = info.claimed_reward;
+			let mut unclaimed_left = unclaimed;
+			let last = destinations.len().saturating_sub(1);
+			let mut slice_rewards = Vec::with_capacity(destinations.len());
+			for (index, (_, share)) in destinations.iter().enumerate() {
+				let (claimed_reward, unclaimed_reward) = if index == last {
+					(claimed_left, unclaimed_left)
+				} else {
+					(
+						share.mul_floor(info.claimed_reward),
+						share.mul_floor(unclaimed),
+					)
+				};
+				claimed_left = claimed_left.saturating_sub(claimed_reward);
+				unclaimed_left = unclaimed_left.saturating_sub(unclaimed_reward);
+				// Every slice needs to end up with part of the reward
+				ensure!(
+					share.deconstruct() > 0
+						&& !claimed_reward.saturating_add(unclaimed_reward).is_zero(),
+					Error::<T>::InvalidSplit
+				);
+				slice_rewards.push((claimed_reward, unclaimed_reward));
+			}
+
+			let slices = destinations.len() as u32;
+			let mut relay_rewards_left = info.contributed_relay_addresses.clone();
+			AccountsPayable::<T>::remove(&source);
+			// Each relay account is now held by every slice
+			if last > 0 {
+				for (relay_account, _) in &info.contributed_relay_addresses {
+					RelayAccountSlices::<T>::mutate(relay_account, |slices| {
+						*slices = Some(slices.unwrap_or_default().saturating_add(last as u32))
+					});
+				}
+			}
+			for (index, ((destination, share), (claimed_reward, unclaimed_reward))) in
+				destinations.into_iter().zip(slice_rewards).enumerate()
+			{
+				let contributed_relay_addresses = info
+					.contributed_relay_addresses
+					.iter()
+					.zip(relay_rewards_left.iter_mut())
+					.map(|((relay_account, reward), (_, reward_left))| {
+						// Contributions of unknown size stay unknown in every slice
+						let slice_reward = match (reward, reward_left.as_mut()) {
+							(Some(reward), Some(reward_left)) => {
+								let slice_reward = if index == last {
+									*reward_left
+								} else {
+									share.mul_floor(*reward)
+								};
+								*reward_left = reward_left.saturating_sub(slice_reward);
+								Some(slice_reward)
+							}
+							_ => None,
+						};
+						(relay_account.clone(), slice_reward)
+					})
+					.collect();
+
+				let slice = RewardInfo {
+					total_reward: claimed_reward.saturating_add(unclaimed_reward),
+					claimed_reward,
+					contributed_relay_addresses,
+				};
+				AccountsPayable::<T>::insert(&destination, &slice);
+
+				Self::deposit_event(Event::RewardSplit {
+					source: source.clone(),
+					destination,
+					total_reward: slice.total_reward,
+					claimed_reward,
+				});
+			}
+
+			Ok(Some(T::WeightInfo::split_reward(
+				slices,
+				info.contributed_relay_addresses.len() as u32,
+			))
+			.into())
+		}
+
+		/// Enable or disable transfer_position
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::set_transfers_enabled())]
+		pub fn set_transfers_enabled(
+			origin: OriginFor<T>,
+			enabled: bool,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			TransfersEnabled::<T>::put(enabled);
+
+			Self::deposit_event(Event::TransfersEnabledSet { enabled });
+
+			Ok(Default::default())
+		}
+
+		/// Transfer a share of the reward of the caller to dest, eg for OTC deals.
+		///
+		/// The claimed and unclaimed parts are transferred in the same proportion, so dest keeps
+		/// vesting on the same schedule instead of receiving funds that are vested right away.
+		/// If dest already has a reward, it needs to have approved the merge with approve_merge.
+		/// The contributed relay addresses are not transferred, so they can not be used to
+		/// change the reward address of dest.
+		/// Transfers can not be queued, so they are rejected while RewardAddressChangeDelay is set
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::transfer_position())]
+		pub fn transfer_position(
+			origin: OriginFor<T>,
+			dest: T::AccountId,
+			share: Perbill,
+		) -> DispatchResultWithPostInfo {
+			let source = ensure_signed(origin)?;
+			Self::ensure_not_paused(PausableCall::UpdateRewardAddress)?;
+			ensure!(TransfersEnabled::<T>::get(), Error::<T>::TransfersDisabled);
+			Self::ensure_reward_can_move_now(&source)?;
+			ensure!(
+				source != dest && !share.is_zero(),
+				Error::<T>::InvalidTransfer
+			);
+			let mut info =
+				AccountsPayable::<T>::get(&source).ok_or(Error::<T>::NoAssociatedClaim)?;
+			ensure!(
+				!StakedUnvestedRewards::<T>::contains_key(&source),
+				Error::<T>::RewardsStaked
+			);
+			Self::ensure_can_receive_reward(&dest, &source)?;
+
+			let claimed_reward = share.mul_floor(info.claimed_reward);
+			let total_reward = claimed_reward.saturating_add(
+				share.mul_floor(info.total_reward.saturating_sub(info.claimed_reward)),
+			);
+			info.total_reward = info.total_reward.saturating_sub(total_reward);
+			info.claimed_reward = info.claimed_reward.saturating_sub(claimed_reward);
+			// The source keeps the rest of each contribution. Rounding leftovers go to the last
+			// one, so that they keep adding up to its reward
+			let all_known = info
+				.contributed_relay_addresses
+				.iter()
+				.all(|(_, reward)| reward.is_some());
+			let last = info.contributed_relay_addresses.len().saturating_sub(1);
+			let mut reward_left = info.total_reward;
+			for (index, (_, reward)) in info.contributed_relay_addresses.iter_mut().enumerate() {
+				if let Some(reward) = reward {
+					*reward = if all_known && index == last {
+						reward_left
+					} else {
+						reward.saturating_sub(share.mul_floor(*reward))
+					};
+					reward_left = reward_left.saturating_sub(*reward);
+				}
+			}
+			if info.total_reward.is_zero() {
+				AccountsPayable::<T>::remove(&source);
+			} else {
+				AccountsPayable::<T>::insert(&source, &info);
+			}
+
+			let received = match AccountsPayable::<T>::get(&dest) {
+				Some(mut existing) => {
+					MergeApprovals::<T>::remove(&dest, &source);
+					existing.total_reward = existing.total_reward.saturating_add(total_reward);
+					existing.claimed_reward =
+						existing.claimed_reward.saturating_add(claimed_reward);
+					existing
+				}
+				None => RewardInfo {
+					total_reward,
+					claimed_reward,
+					contributed_relay_addresses: Vec::new(),
+				},
+			};
+			AccountsPayable::<T>::insert(&dest, &received);
+
+			Self::deposit_event(Event::PositionTransferred {
+				source,
+				dest,
+				total_reward,
+				claimed_reward,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Update reward address, proving that the caller owns the current native key
+		///
+		/// If new_reward_account already has a reward, both are merged as long as
+		/// new_reward_account approved it with approve_merge.
+		/// If RewardAddressChangeDelay is not zero the change is only queued, and needs to be
+		/// applied with execute_pending_change once the delay is over.
+		/// relay_addresses needs to be at least the number of relay addresses of both rewards, as
+		/// the weight depends on it
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::update_reward_address(*relay_addresses))]
+		pub fn update_reward_address(
+			origin: OriginFor<T>,
+			new_reward_account: T::AccountId,
+			relay_addresses: u32,
+		) -> DispatchResultWithPostInfo {
+			let signer = ensure_signed(origin)?;
+			Self::ensure_not_paused(PausableCall::UpdateRewardAddress)?;
+
+			// Calculate the veted amount on demand.
+			let info = AccountsPayable::<T>::get(&signer).ok_or(Error::<T>::NoAssociatedClaim)?;
+			ensure!(
+				!StakedUnvestedRewards::<T>::contains_key(&signer),
+				Error::<T>::RewardsStaked
+			);
+
+			// An existing account needs to have approved the merge
+			Self::ensure_can_receive_reward(&new_reward_account, &signer)?;
+
+			let delay = T::RewardAddressChangeDelay::get();
+			if !delay.is_zero() {
+				ensure!(
+					!PendingRewardAddressChanges::<T>::contains_key(&signer),
+					Error::<T>::RewardAddressChangePending
+				);
+				let executable_at = frame_system::Pallet::<T>::block_number().saturating_add(delay);
+				PendingRewardAddressChanges::<T>::insert(
+					&signer,
+					PendingRewardAddressChange {
+						new_account: new_reward_account.clone(),
+						executable_at,
+					},
+				);
+
+				Self::deposit_event(Event::RewardAddressChangeQueued {
+					previous_account: signer,
+					new_account: new_reward_account,
+					executable_at,
+				});
+
+				// Nothing is moved until the change is executed
+				return Ok(Some(T::WeightInfo::update_reward_address(0)).into());
+			}
+
+			let relay_addresses =
+				Self::moved_relay_addresses(&info, &new_reward_account, relay_addresses)?;
+			Self::move_reward_info(&signer, &new_reward_account, info)?;
+
+			// Emit event
+			Self::deposit_event(Event::RewardAddressUpdated {
+				previous_account: signer,
+				new_account: new_reward_account,
+				mechanism: RewardAddressChangeMechanism::SelfUpdate,
+			});
+
+			Ok(Some(T::WeightInfo::update_reward_address(relay_addresses)).into())
+		}
+
+		/// Cancel the reward address change queued by the caller
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::cancel_reward_address_change())]
+		pub fn cancel_reward_address_change(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let pending = PendingRewardAddressChanges::<T>::take(&owner)
+				.ok_or(Error::<T>::NoPendingRewardAddressChange)?;
+
+			Self::deposit_event(Event::RewardAddressChangeCancelled {
+				previous_account: owner,
+				new_account: pending.new_account,
+				relay_account: None,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Cancel the reward address change queued for previous_account, with a signature of one
+		/// of the relay accounts that contributed to its reward. The signature covers the block
+		/// the change is executable at, so it can't cancel a change queued again later.
+		///
+		/// This allows contributors to stop a change made with a compromised native key
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::cancel_reward_address_change_with_relay_key())]
+		pub fn cancel_reward_address_change_with_relay_key(
+			origin: OriginFor<T>,
+			previous_account: T::AccountId,
+			relay_account: T::RelayChainAccountId,
+			proof: MultiSignature,
+		) -> DispatchResultWithPostInfo {
+			T::RewardAddressChangeOrigin::ensure_origin(origin)?;
+			let pending = PendingRewardAddressChanges::<T>::get(&previous_account)
+				.ok_or(Error::<T>::NoPendingRewardAddressChange)?;
+			let info = AccountsPayable::<T>::get(&previous_account)
+				.ok_or(Error::<T>::NoAssociatedClaim)?;
+			ensure!(
+				info.contributed_relay_addresses
+					.iter()
+					.any(|(contributor, _)| contributor == &relay_account),
+				Error::<T>::NonContributedAddressProvided
+			);
+
+			// b"<Bytes>" + "SignatureNetworkIdentifier" + b"cancel" + "previous_account"
+			// + "new_account" + "executable_at" + b"</Bytes>"
+			let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+			payload.append(&mut T::SignatureNetworkIdentifier::get().to_vec());
+			payload.append(&mut CANCEL_CHANGE_BYTES.to_vec());
+			payload.append(&mut previous_account.encode());
+			payload.append(&mut pending.new_account.encode());
+			payload.append(&mut pending.executable_at.encode());
+			payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+			ensure!(
+				proof.verify(payload.as_slice(), &relay_account.clone().into()),
+				Error::<T>::InvalidClaimSignature
+			);
+
+			PendingRewardAddressChanges::<T>::remove(&previous_account);
+
+			Self::deposit_event(Event::RewardAddressChangeCancelled {
+				previous_account,
+				new_account: pending.new_account,
+				relay_account: Some(relay_account),
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Apply the reward address change queued for previous_account once its delay is over.
+		///
+		/// Anyone can execute it, the same checks as in update_reward_address are made again
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::execute_pending_change(*relay_addresses))]
+		pub fn execute_pending_change(
+			origin: OriginFor<T>,
+			previous_account: T::AccountId,
+			relay_addresses: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::ensure_not_paused(PausableCall::UpdateRewardAddress)?;
+			let pending = PendingRewardAddressChanges::<T>::get(&previous_account)
+				.ok_or(Error::<T>::NoPendingRewardAddressChange)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= pending.executable_at,
+				Error::<T>::RewardAddressChangeNotExecutableYet
+			);
+
+			let info = AccountsPayable::<T>::get(&previous_account)
+				.ok_or(Error::<T>::NoAssociatedClaim)?;
+			ensure!(
+				!StakedUnvestedRewards::<T>::contains_key(&previous_account),
+				Error::<T>::RewardsStaked
+			);
+			Self::ensure_can_receive_reward(&pending.new_account, &previous_account)?;
+			let relay_addresses =
+				Self::moved_relay_addresses(&info, &pending.new_account, relay_addresses)?;
+
+			// Also removes the pending change
+			Self::move_reward_info(&previous_account, &pending.new_account, info)?;
+
+			Self::deposit_event(Event::RewardAddressUpdated {
+				previous_account,
+				new_account: pending.new_account,
+				mechanism: RewardAddressChangeMechanism::SelfUpdate,
+			});
+
+			Ok(Some(T::WeightInfo::execute_pending_change(relay_addresses)).into())
+		}
+
+		/// Request moving the reward of lost_account, whose native key was lost, to new_account.
+		///
+		/// evidence_hash identifies the off-chain evidence that lost_account belongs to the
+		/// requester, eg relay multisig or proxy records. RecoveryDeposit is reserved from the
+		/// caller until the request is resolved
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::request_recovery())]
+		pub fn request_recovery(
+			origin: OriginFor<T>,
+			lost_account: T::AccountId,
+			new_account: T::AccountId,
+			evidence_hash: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			let requester = ensure_signed(origin)?;
+			ensure!(
+				AccountsPayable::<T>::contains_key(&lost_account),
+				Error::<T>::NoAssociatedClaim
+			);
+			ensure!(
+				!RecoveryRequests::<T>::contains_key(&lost_account),
+				Error::<T>::RecoveryAlreadyRequested
+			);
+			Self::ensure_can_receive_reward(&new_account, &lost_account)?;
+
+			let deposit = T::RecoveryDeposit::get();
+			T::RewardCurrency::reserve(&requester, deposit)?;
+			RecoveryRequests::<T>::insert(
+				&lost_account,
+				RecoveryRequest {
+					requester: requester.clone(),
+					new_account: new_account.clone(),
+					evidence_hash,
+					deposit,
+					executable_at: None,
+				},
+			);
+
+			Self::deposit_event(Event::RecoveryRequested {
+				lost_account,
+				new_account,
+				requester,
+				evidence_hash,
+				deposit,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Approve the recovery of lost_account after reviewing its evidence.
+		///
+		/// The recovery can be finalized once RecoveryDelay is over
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::approve_recovery())]
+		pub fn approve_recovery(
+			origin: OriginFor<T>,
+			lost_account: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let executable_at =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::RecoveryDelay::get());
+			RecoveryRequests::<T>::try_mutate(&lost_account, |request| -> DispatchResult {
+				let request = request.as_mut().ok_or(Error::<T>::NoRecoveryRequest)?;
+				ensure!(
+					request.executable_at.is_none(),
+					Error::<T>::RecoveryAlreadyApproved
+				);
+				request.executable_at = Some(executable_at);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::RecoveryApproved {
+				lost_account,
+				executable_at,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Reject the recovery of lost_account, slashing the deposit
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::reject_recovery())]
+		pub fn reject_recovery(
+			origin: OriginFor<T>,
+			lost_account: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let request =
+				RecoveryRequests::<T>::take(&lost_account).ok_or(Error::<T>::NoRecoveryRequest)?;
+			let (imbalance, _) =
+				T::RewardCurrency::slash_reserved(&request.requester, request.deposit);
+			drop(imbalance);
+
+			Self::deposit_event(Event::RecoveryCancelled {
+				lost_account,
+				slashed: request.deposit,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Cancel the recovery of lost_account.
+		///
+		/// The requester can withdraw the request and get the deposit back. The owner of
+		/// lost_account can veto it, proving the key was not lost, in which case the deposit is
+		/// slashed
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::cancel_recovery())]
+		pub fn cancel_recovery(
+			origin: OriginFor<T>,
+			lost_account: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let request =
+				RecoveryRequests::<T>::get(&lost_account).ok_or(Error::<T>::NoRecoveryRequest)?;
+
+			let slashed = if who == lost_account {
+				let (imbalance, _) =
+					T::RewardCurrency::slash_reserved(&request.requester, request.deposit);
+				drop(imbalance);
+				request.deposit
+			} else {
+				ensure!(who == request.requester, DispatchError::BadOrigin);
+				T::RewardCurrency::unreserve(&request.requester, request.deposit);
+				Zero::zero()
+			};
+			RecoveryRequests::<T>::remove(&lost_account);
+
+			Self::deposit_event(Event::RecoveryCancelled {
+				lost_account,
+				slashed,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Move the reward of lost_account to the new account of its approved recovery, once the
+		/// delay is over, and return the deposit.
+		///
+		/// Anyone can finalize it, unless update_reward_address is paused. relay_addresses needs
+		/// to be at least the number of relay addresses of both rewards, as the weight depends on it
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::finalize_recovery(*relay_addresses))]
+		pub fn finalize_recovery(
+			origin: OriginFor<T>,
+			lost_account: T::AccountId,
+			relay_addresses: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::ensure_not_paused(PausableCall::UpdateRewardAddress)?;
+			let request =
+				RecoveryRequests::<T>::get(&lost_account).ok_or(Error::<T>::NoRecoveryRequest)?;
+			let executable_at = request
+				.executable_at
+				.ok_or(Error::<T>::RecoveryNotApproved)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= executable_at,
+				Error::<T>::RecoveryNotExecutableYet
+			);
+
+			let info =
+				AccountsPayable::<T>::get(&lost_account).ok_or(Error::<T>::NoAssociatedClaim)?;
+			// Staked rewards are bonded from the lost account, so they can not be moved
+			ensure!(
+				!StakedUnvestedRewards::<T>::contains_key(&lost_account),
+				Error::<T>::RewardsStaked
+			);
+			Self::ensure_can_receive_reward(&request.new_account, &lost_account)?;
+			let relay_addresses =
+				Self::moved_relay_addresses(&info, &request.new_account, relay_addresses)?;
+
+			Self::move_reward_info(&lost_account, &request.new_account, info)?;
+			RecoveryRequests::<T>::remove(&lost_account);
+			T::RewardCurrency::unreserve(&request.requester, request.deposit);
+
+			Self::deposit_event(Event::RecoveryFinalized {
+				lost_account: lost_account.clone(),
+				new_account: request.new_account.clone(),
+				evidence_hash: request.evidence_hash,
+			});
+			Self::deposit_event(Event::RewardAddressUpdated {
+				previous_account: lost_account,
+				new_account: request.new_account,
+				mechanism: RewardAddressChangeMechanism::Recovery,
+			});
+
+			Ok(Some(T::WeightInfo::finalize_recovery(relay_addresses)).into())
+		}
+
+		/// Consent to the contribution of relay_account being associated with the caller, when
+		/// RequireNativeConsent is set and the association is not submitted by the caller itself
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::approve_association())]
+		pub fn approve_association(
+			origin: OriginFor<T>,
+			relay_account: T::RelayChainAccountId,
+		) -> DispatchResultWithPostInfo {
+			let account = ensure_signed(origin)?;
+			ensure!(
+				UnassociatedContributions::<T>::contains_key(&relay_account),
+				Error::<T>::NoAssociatedClaim
+			);
+
+			AssociationApprovals::<T>::insert(&account, &relay_account, ());
+
+			Self::deposit_event(Event::AssociationApproved {
+				account,
+				relay_account,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Withdraw a consent given with approve_association
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::cancel_association_approval())]
+		pub fn cancel_association_approval(
+			origin: OriginFor<T>,
+			relay_account: T::RelayChainAccountId,
+		) -> DispatchResultWithPostInfo {
+			let account = ensure_signed(origin)?;
+			ensure!(
+				AssociationApprovals::<T>::take(&account, &relay_account).is_some(),
+				Error::<T>::AssociationNotApproved
+			);
+
+			Self::deposit_event(Event::AssociationApprovalCancelled {
+				account,
+				relay_account,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// This extrinsic completes the initialization if some checks are fullfiled. These checks are:
+		///  -The reward contribution money matches the crowdloan pot
+		///  -The end vesting block is higher than the init vesting block
+		///  -The initialization has not complete yet
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::complete_initialization())]
+		pub fn complete_initialization(
+			origin: OriginFor<T>,
+			lease_ending_block: T::VestingBlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::InitializationOrigin::ensure_origin(origin)?;
+
+			let initialized = <Initialized<T>>::get();
+
+			// This ensures there was no prior initialization
+			ensure!(
+				initialized == false,
+				Error::<T>::RewardVecAlreadyInitialized
+			);
+
+			// This ensures the end vesting block (when all funds are fully vested)
+			// is bigger than the init vesting block
+			ensure!(
+				lease_ending_block > InitVestingBlock::<T>::get(),
+				Error::<T>::VestingPeriodNonValid
+			);
+
+			let current_initialized_rewards = InitializedRewardAmount::<T>::get();
+
+			let reward_difference = Self::pot().saturating_sub(current_initialized_rewards);
+
+			// Ensure the difference is not bigger than the total number of contributors
+			ensure!(
+				reward_difference < TotalContributors::<T>::get().into(),
+				Error::<T>::RewardsDoNotMatchFund
+			);
+
+			// Burn the difference
+			let imbalance = T::RewardCurrency::withdraw(
+				&PALLET_ID.into_account_truncating(),
+				reward_difference,
+				WithdrawReasons::TRANSFER,
+				AllowDeath,
+			)
+			.expect("Shouldnt fail, as the fund should be enough to burn and nothing is locked");
+			drop(imbalance);
+
+			EndVestingBlock::<T>::put(lease_ending_block);
+
+			<Initialized<T>>::put(true);
+
+			Self::deposit_event(Event::InitializationCompleted {
+				end_vesting_block: lease_ending_block,
+				total_contributors: TotalContributors::<T>::get(),
+				initialized_reward_amount: current_initialized_rewards,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Initialize the reward distribution storage. It shortcuts whenever an error is found
+
+		/// This does not enforce any checks other than making sure we dont go over funds
+		/// complete_initialization should perform any additional
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::initialize_reward_vec(rewards.len() as u32))]
+		pub fn initialize_reward_vec(
+			origin: OriginFor<T>,
+			rewards: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)>,
+		) -> DispatchResultWithPostInfo {
+			T::InitializationOrigin::ensure_origin(origin)?;
+			Self::ensure_reward_vec_can_be_initialized(rewards.len())?;
+
+			let incoming_rewards: BalanceOf<T> = rewards
+				.iter()
+				.fold(0u32.into(), |acc: BalanceOf<T>, (_, _, reward)| {
+					acc + *reward
+				});
+
+			// Ensure we dont go over funds
+			ensure!(
+				InitializedRewardAmount::<T>::get() + incoming_rewards <= Self::pot(),
+				Error::<T>::BatchBeyondFundPot
+			);
+
+			let mut inserted_rows = 0u32;
+			for (relay_account, native_account, reward) in &rewards {
+				match Self::initialize_contribution(relay_account, native_account, *reward)? {
+					InitializationOutcome::Inserted
+					| InitializationOutcome::MergedIntoExistingAccount => inserted_rows += 1,
+					_ => (),
+				}
+			}
+
+			// Skipped rows only read ClaimedRelayChainIds and UnassociatedContributions, so they
+			// are charged these reads on top of the benchmarked weight of the inserted ones
+			let skipped_rows = (rewards.len() as u64).saturating_sub(inserted_rows as u64);
+			Ok(Some(
+				T::WeightInfo::initialize_reward_vec(inserted_rows)
+					.saturating_add(T::DbWeight::get().reads(2 * skipped_rows)),
+			)
+			.into())
+		}
+
+		/// Change the total reward of a contributor once the initialization is complete.
+		///
+		/// The reward can never go below what was already claimed. The difference is moved
+		/// between RewardFundingAccount and the pot so that it keeps matching the rewards owed.
+		/// If the reward was contributed by several relay accounts, the contribution of each of
+		/// them becomes unknown.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::adjust_reward())]
+		pub fn adjust_reward(
+			origin: OriginFor<T>,
+			contributor: ContributorAccount<T::RelayChainAccountId, T::AccountId>,
+			new_total_reward: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let initialized = <Initialized<T>>::get();
+			ensure!(initialized, Error::<T>::RewardVecNotFullyInitializedYet);
+
+			let mut reward_info =
+				Self::contributor_reward_info(&contributor).ok_or(Error::<T>::NoAssociatedClaim)?;
+			ensure!(
+				new_total_reward >= reward_info.claimed_reward,
+				Error::<T>::RewardBelowClaimedAmount
+			);
+
+			let previous_total_reward = reward_info.total_reward;
+			if new_total_reward > previous_total_reward {
+				let increase = new_total_reward - previous_total_reward;
+				Self::fund_pot(increase)?;
+				InitializedRewardAmount::<T>::mutate(|amount| *amount += increase);
+			} else {
+				let decrease = previous_total_reward - new_total_reward;
+				Self::refund_from_pot(decrease)?;
+				InitializedRewardAmount::<T>::mutate(|amount| {
+					*amount = amount.saturating_sub(decrease)
+				});
+			}
+
+			reward_info.total_reward = new_total_reward;
+			// A single relay account contributed the whole reward, otherwise there is no telling
+			// which of the contributions the adjustment is for
+			if new_total_reward != previous_total_reward {
+				match reward_info.contributed_relay_addresses.as_mut_slice() {
+					[(_, reward)] => *reward = Some(new_total_reward),
+					relay_rewards => {
+						for (_, reward) in relay_rewards {
+							*reward = None;
+						}
+					}
+				}
+			}
+			Self::insert_contributor_reward_info(&contributor, &reward_info);
+
+			Self::deposit_event(Event::RewardAdjusted {
+				contributor,
+				previous_reward: previous_total_reward,
+				new_reward: new_total_reward,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Add a contribution that was left out of the initialization.
+		///
+		/// The pot is topped up with the reward from RewardFundingAccount, and the contributor
+		/// vests on the same schedule
+		/// as everybody else, so whatever is already vested can be claimed right away.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::add_late_contributor())]
+		pub fn add_late_contributor(
+			origin: OriginFor<T>,
+			relay_account: T::RelayChainAccountId,
+			native_account: Option<T::AccountId>,
+			reward: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let initialized = <Initialized<T>>::get();
+			ensure!(initialized, Error::<T>::RewardVecNotFullyInitializedYet);
+
+			// Unlike in the initialization, we want to know why the contribution was not inserted
+			ensure!(
+				ClaimedRelayChainIds::<T>::get(&relay_account).is_none()
+					&& UnassociatedContributions::<T>::get(&relay_account).is_none(),
+				Error::<T>::AlreadyAssociated
+			);
+			ensure!(
+				reward >= T::MinimumReward::get(),
+				Error::<T>::RewardNotHighEnough
+			);
+
+			Self::fund_pot(reward)?;
+			Self::initialize_contribution(&relay_account, &native_account, reward)?;
+
+			Self::deposit_event(Event::LateContributorAdded {
+				relay_account,
+				native_account,
+				reward,
+			});
+
+			Ok(Default::default())
+		}
+
+		/// Remove a contributor once the initialization is complete.
+		///
+		/// Whatever was not claimed yet goes back from the pot to RewardFundingAccount. The relay
+		/// accounts of the entry are released, so a corrected contribution can be added again with
+		/// add_late_contributor. Pending address changes, recovery requests and merge approvals of
+		/// the contributor are dropped along with it. Contributors with staked rewards can not be
+		/// removed until they unbond them.
+		///
+		/// relay_addresses needs to be at least the number of relay addresses of the contributor,
+		/// as the weight depends on it
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::remove_contributor(*relay_addresses))]
+		pub fn remove_contributor(
+			origin: OriginFor<T>,
+			contributor: ContributorAccount<T::RelayChainAccountId, T::AccountId>,
+			relay_addresses: u32,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let initialized = <Initialized<T>>::get();
+			ensure!(initialized, Error::<T>::RewardVecNotFullyInitializedYet);
+
+			let reward_info =
+				Self::contributor_reward_info(&contributor).ok_or(Error::<T>::NoAssociatedClaim)?;
+			ensure!(
+				reward_info.contributed_relay_addresses.len() as u32 <= relay_addresses,
+				Error::<T>::TooFewRelayAddresses
+			);
+			if let ContributorAccount::Native(native_account) = &contributor {
+				ensure!(
+					!StakedUnvestedRewards::<T>::contains_key(native_account),
+					Error::<T>::RewardsStaked
+				);
+			}
+
+			let unclaimed_reward = reward_info
+				.total_reward
+				.saturating_sub(reward_info.claimed_reward);
+			Self::refund_from_pot(unclaimed_reward)?;
+
+			let relay_addresses = reward_info.contributed_relay_addresses.len() as u32;
+			InitializedRewardAmount::<T>::mutate(|amount| {
+				*amount = amount.saturating_sub(unclaimed_reward)
+			});
+
+			// Relay accounts still held by other slices of a split reward are not released
+			let released = match &contributor {
+				ContributorAccount::Relay(relay_account) => {
+					UnassociatedContributions::<T>::remove(relay_account);
+					relay_addresses
+				}
+				ContributorAccount::Native(native_account) => {
+					AccountsPayable::<T>::remove(native_account);
+					Self::clear_reward_account_requests(native_account);
+					let mut released = 0u32;
+					for (relay_account, _) in &reward_info.contributed_relay_addresses {
+						if Self::drop_relay_account_holder(relay_account) {
+							ClaimedRelayChainIds::<T>::remove(relay_account);
+							released += 1;
+						}
+					}
+					released
+				}
+			};
+			TotalContributors::<T>::mutate(|contributors| {
+				*contributors = contributors.saturating_sub(released)
+			});
+
+			Self::deposit_event(Event::ContributorRemoved {
+				contributor,
+				refunded: unclaimed_reward,
+			});
+
+			Ok(Some(T::WeightInfo::remove_contributor(relay_addresses)).into())
+		}
+
+		/// Pause a call, eg if a bug or a compromised key is discovered.
+		///
+		/// Vesting is computed from the vesting block, so rewards keep vesting while paused and
+		/// can be claimed as usual once the call is unpaused
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(origin: OriginFor<T>, call: PausableCall) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(
+				Paused::<T>::get(call).is_none(),
+				Error::<T>::CallAlreadyPaused
+			);
+
+			Paused::<T>::insert(call, ());
+
+			Self::deposit_event(Event::CallPaused { call });
+
+			Ok(Default::default())
+		}
+
+		/// Unpause a previously paused call
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::unpause())]
+		pub fn unpause(origin: OriginFor<T>, call: PausableCall) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(Paused::<T>::get(call).is_some(), Error::<T>::CallNotPaused);
+
+			Paused::<T>::remove(call);
+
+			Self::deposit_event(Event::CallUnpaused { call });
+
+			Ok(Default::default())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account ID that holds the Crowdloan's funds
+		pub fn account_id() -> T::AccountId {
+			PALLET_ID.into_account_truncating()
+		}
+		/// The Account Id's balance
+		pub fn pot() -> BalanceOf<T> {
+			T::RewardCurrency::free_balance(&Self::account_id())
+		}
+		/// Simulate initialize_reward_vec with the given rewards without modifying any storage.
+		/// Returns what would happen to each of the rows and the resulting totals. If the batch
+		/// goes beyond the pot, initialize_reward_vec rejects it entirely, so every row is marked
+		/// as WouldExceedPot and the totals are the current ones.
+		pub fn simulate_initialize(
+			rewards: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)>,
+		) -> Result<InitializationSimulation<BalanceOf<T>>, DispatchError> {
+			Self::ensure_reward_vec_can_be_initialized(rewards.len())?;
+
+			let incoming_rewards: BalanceOf<T> = rewards
+				.iter()
+				.fold(0u32.into(), |acc: BalanceOf<T>, (_, _, reward)| {
+					acc.saturating_add(*reward)
+				});
+			if InitializedRewardAmount::<T>::get().saturating_add(incoming_rewards) > Self::pot() {
+				return Ok(InitializationSimulation {
+					outcomes: vec![InitializationOutcome::WouldExceedPot; rewards.len()],
+					initialized_reward_amount: InitializedRewardAmount::<T>::get(),
+					total_contributors: TotalContributors::<T>::get(),
+					pot: Self::pot(),
+				});
+			}
+
+			with_transaction(|| {
+				let mut outcomes = Vec::with_capacity(rewards.len());
+				for (relay_account, native_account, reward) in &rewards {
+					match Self::initialize_contribution(relay_account, native_account, *reward) {
+						Ok(outcome) => outcomes.push(outcome),
+						Err(e) => return TransactionOutcome::Rollback(Err(e)),
+					}
+				}
+
+				// Nothing of this should be persisted
+				TransactionOutcome::Rollback(Ok(InitializationSimulation {
+					outcomes,
+					initialized_reward_amount: InitializedRewardAmount::<T>::get(),
+					total_contributors: TotalContributors::<T>::get(),
+					pot: Self::pot(),
+				}))
+			})
+		}
+
+		/// The relay account of a pallet_multisig multisig with the given signatories and threshold
+		pub fn multisig_account_id(
+			signatories: &[T::RelayChainAccountId],
+			threshold: u16,
+		) -> T::RelayChainAccountId {
+			let mut signatories: Vec<AccountId32> = signatories
+				.iter()
+				.map(|signatory| signatory.clone().into())
+				.collect();
+			signatories.sort();
+			let entropy =
+				(MULTISIG_ACCOUNT_PREFIX, signatories, threshold).using_encoded(blake2_256);
+			AccountId32::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+				.expect("infinite length input; no invalid inputs for type; qed")
+				.into()
+		}
+
+		/// Unassociated reward of relay_account, ensuring it can be associated with reward_account
+		fn associable_reward_info(
+			reward_account: &T::AccountId,
+			relay_account: &T::RelayChainAccountId,
+		) -> Result<RewardInfo<T>, DispatchError> {
+			// The relay account should be unassociated
+			let reward_info = UnassociatedContributions::<T>::get(relay_account)
+				.ok_or(Error::<T>::NoAssociatedClaim)?;
+
+			// We ensure the relay chain id wast not yet associated to avoid multi-claiming
+			// We dont need this right now, as it will always be true if the above check is true
+			ensure!(
+				ClaimedRelayChainIds::<T>::get(relay_account).is_none(),
+				Error::<T>::AlreadyAssociated
+			);
+
+			// For now I prefer that we dont support providing an existing account here
+			ensure!(
+				AccountsPayable::<T>::get(reward_account).is_none(),
+				Error::<T>::AlreadyAssociated
+			);
+			Ok(reward_info)
+		}
+
+		/// Ensure reward_account consents to the association of relay_account when
+		/// RequireNativeConsent is set, either by being the origin, with a consent_proof signature
+		/// over relay_account or through approve_association. A given approval is used up, and
+		/// only when there is no consent_proof
+		fn ensure_native_consent(
+			origin: OriginFor<T>,
+			reward_account: &T::AccountId,
+			relay_account: &T::RelayChainAccountId,
+			consent_proof: Option<T::NativeSignature>,
+		) -> DispatchResult {
+			if T::RequireNativeConsent::get()
+				&& consent_proof.is_none()
+				&& ensure_signed(origin.clone()).ok().as_ref() != Some(reward_account)
+			{
+				ensure!(
+					AssociationApprovals::<T>::take(reward_account, relay_account).is_some(),
+					Error::<T>::AssociationNotApproved
+				);
+				return Ok(());
+			}
+			Self::ensure_signed_consent(
+				origin,
+				reward_account,
+				relay_account.encode(),
+				consent_proof,
+			)
+		}
+
+		/// Ensure reward_account consents to receive the reward of subject when
+		/// RequireNativeConsent is set, either by being the origin or with a consent_proof
+		/// signature
+		fn ensure_signed_consent(
+			origin: OriginFor<T>,
+			reward_account: &T::AccountId,
+			mut subject: Vec<u8>,
+			consent_proof: Option<T::NativeSignature>,
+		) -> DispatchResult {
+			if !T::RequireNativeConsent::get() {
+				return Ok(());
+			}
+			if ensure_signed(origin).ok().as_ref() == Some(reward_account) {
+				return Ok(());
+			}
+			let consent_proof = consent_proof.ok_or(Error::<T>::AssociationNotApproved)?;
+
+			// b"<Bytes>" + "SignatureNetworkIdentifier" + b"consent" + "subject" + b"</Bytes>"
+			let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+			payload.append(&mut T::SignatureNetworkIdentifier::get().to_vec());
+			payload.append(&mut CONSENT_BYTES.to_vec());
+			payload.append(&mut subject);
+			payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+			ensure!(
+				consent_proof.verify(payload.as_slice(), reward_account),
+				Error::<T>::InvalidConsentSignature
+			);
+			Ok(())
+		}
+
+		/// What relay accounts sign to associate a contribution with reward_account
+		fn association_payload(reward_account: &T::AccountId) -> Vec<u8> {
+			// b"<Bytes>" "SignatureNetworkIdentifier" + "new_account" + b"</Bytes>"
 			let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
 			payload.append(&mut T::SignatureNetworkIdentifier::get().to_vec());
 			payload.append(&mut reward_account.encode());
-			payload.append(&mut previous_account.encode());
 			payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+			payload
+		}
 
-			// Get the reward info for the account to be changed
-			let reward_info = AccountsPayable::<T>::get(&previous_account)
-				.ok_or(Error::<T>::NoAssociatedClaim)?;
+		/// Associate the contribution of relay_account with reward_account, making the first
+		/// payment. Returns the first payment
+		fn associate(
+			reward_account: T::AccountId,
+			relay_account: T::RelayChainAccountId,
+			mut reward_info: RewardInfo<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			// Make the first payment
+			let first_payment = T::InitializationPayment::get() * reward_info.total_reward;
+
+			T::RewardCurrency::transfer(
+				&PALLET_ID.into_account_truncating(),
+				&reward_account,
+				first_payment,
+				AllowDeath,
+			)?;
 
-			Self::verify_signatures(proofs, reward_info.clone(), payload)?;
+			Self::deposit_event(Event::InitialPaymentMade {
+				account: reward_account.clone(),
+				amount: first_payment,
+			});
 
-			// Remove fromon payable
-			AccountsPayable::<T>::remove(&previous_account);
+			reward_info.claimed_reward = first_payment;
 
 			// Insert on payable
 			AccountsPayable::<T>::insert(&reward_account, &reward_info);
 
+			// Remove from unassociated
+			<UnassociatedContributions<T>>::remove(&relay_account);
+
+			// Insert in mapping
+			ClaimedRelayChainIds::<T>::insert(&relay_account, ());
+
 			// Emit Event
-			Self::deposit_event(Event::RewardAddressUpdated(
-				previous_account,
-				reward_account,
-			));
+			Self::deposit_event(Event::NativeIdentityAssociated {
+				relay_account,
+				account: reward_account,
+				total_reward: reward_info.total_reward,
+			});
 
-			Ok(Default::default())
+			Ok(first_payment)
 		}
 
-		/// Collect whatever portion of your reward are currently vested.
-		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::claim())]
-		pub fn claim(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
-			let payee = ensure_signed(origin)?;
+		/// Pay whatever portion of the reward of payee is currently vested
+		fn do_claim(payee: T::AccountId) -> Result<BalanceOf<T>, DispatchError> {
+			let info = Self::claimable_reward_info(&payee)?;
+			let payable_amount = Self::vested_unclaimed_reward(&info);
+			Self::pay_reward(payee, info, payable_amount)?;
+			Ok(payable_amount)
+		}
+
+		/// Reward info of owner, ensuring it can claim right now
+		fn claimable_reward_info(owner: &T::AccountId) -> Result<RewardInfo<T>, DispatchError> {
+			Self::ensure_not_paused(PausableCall::Claim)?;
 			let initialized = <Initialized<T>>::get();
 			ensure!(initialized, Error::<T>::RewardVecNotFullyInitializedYet);
 			// Calculate the veted amount on demand.
-			let mut info =
-				AccountsPayable::<T>::get(&payee).ok_or(Error::<T>::NoAssociatedClaim)?;
+			let info = AccountsPayable::<T>::get(owner).ok_or(Error::<T>::NoAssociatedClaim)?;
 			ensure!(
 				info.claimed_reward < info.total_reward,
 				Error::<T>::RewardsAlreadyClaimed
 			);
+			Ok(info)
+		}
+
+		/// Portion of the reward that is vested but has not been claimed yet
+		fn vested_unclaimed_reward(info: &RewardInfo<T>) -> BalanceOf<T> {
+			// Rewards might have been adjusted below what was already paid
+			Self::vested_reward(info).saturating_sub(info.claimed_reward)
+		}
 
+		/// Portion of the reward that is vested by now, including what was already claimed
+		fn vested_reward(info: &RewardInfo<T>) -> BalanceOf<T> {
 			// Get the current block used for vesting purposes
 			let now = T::VestingBlockProvider::current_block_number();
 
@@ -355,124 +2126,331 @@ pub mod pallet {
 					/ period.into()
 			};
 
-			// If the period is bigger than whats missing to pay, then return whats missing to pay
-			let payable_amount = if should_have_claimed >= (info.total_reward - first_paid) {
-				info.total_reward.saturating_sub(info.claimed_reward)
+			// If the period is bigger than whats missing to pay, then return the whole reward
+			if should_have_claimed >= (info.total_reward - first_paid) {
+				info.total_reward
 			} else {
-				should_have_claimed + first_paid - info.claimed_reward
+				should_have_claimed + first_paid
+			}
+		}
+
+		/// Pay the vested rewards of up to max_payouts accounts, resuming from where the previous
+		/// call left off, without consuming more than remaining_weight
+		pub(crate) fn do_auto_payouts(max_payouts: u32, remaining_weight: Weight) -> Weight {
+			// Initialized, Paused and AutoPayoutCursor
+			let mut consumed_weight = T::DbWeight::get().reads(3);
+			if max_payouts == 0 || remaining_weight.any_lt(consumed_weight) {
+				return Weight::zero();
+			}
+			if !<Initialized<T>>::get() || Self::ensure_not_paused(PausableCall::Claim).is_err() {
+				return consumed_weight;
+			}
+
+			let mut cursor = AutoPayoutCursor::<T>::get();
+			let mut accounts = match cursor.clone() {
+				Some(cursor) => AccountsPayable::<T>::iter_from(cursor),
+				None => AccountsPayable::<T>::iter(),
 			};
+			let payout_weight = T::WeightInfo::auto_payout();
+			let mut visited = 0u32;
+			while visited < max_payouts
+				&& !remaining_weight.any_lt(consumed_weight.saturating_add(payout_weight))
+			{
+				let (account, info) = match accounts.next() {
+					Some(entry) => entry,
+					None => {
+						// Reached the end, start over in the next block
+						cursor = None;
+						break;
+					}
+				};
+				visited += 1;
+				consumed_weight = consumed_weight.saturating_add(payout_weight);
+				cursor = Some(AccountsPayable::<T>::hashed_key_for(&account));
+
+				if info.claimed_reward >= info.total_reward {
+					continue;
+				}
+				let payable_amount = Self::vested_unclaimed_reward(&info);
+				if payable_amount.is_zero() || payable_amount < T::MinimumAutoPayout::get() {
+					continue;
+				}
+				// A failed payout must not leave the entry marked as claimed
+				let _ = with_storage_layer(|| Self::pay_reward(account, info, payable_amount));
+			}
+
+			match cursor {
+				Some(cursor) => AutoPayoutCursor::<T>::put(cursor),
+				None => AutoPayoutCursor::<T>::kill(),
+			}
+			consumed_weight.saturating_add(T::DbWeight::get().writes(1))
+		}
+
+		/// Transfer amount from the pot to payee, recording it as claimed
+		fn pay_reward(
+			payee: T::AccountId,
+			info: RewardInfo<T>,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let info = Self::transfer_reward(&payee, &payee, info, amount)?;
+			// Emit event
+			Self::deposit_event(Event::RewardsPaid {
+				account: payee,
+				amount,
+				total_claimed: info.claimed_reward,
+				remaining_unvested: info.total_reward.saturating_sub(Self::vested_reward(&info)),
+				vesting_block: T::VestingBlockProvider::current_block_number(),
+			});
+			Ok(())
+		}
 
-			info.claimed_reward = info.claimed_reward.saturating_add(payable_amount);
-			AccountsPayable::<T>::insert(&payee, &info);
+		/// Transfer amount from the pot to dest, recording it as claimed by owner.
+		/// Returns the updated reward info
+		fn transfer_reward(
+			owner: &T::AccountId,
+			dest: &T::AccountId,
+			mut info: RewardInfo<T>,
+			amount: BalanceOf<T>,
+		) -> Result<RewardInfo<T>, DispatchError> {
+			info.claimed_reward = info.claimed_reward.saturating_add(amount);
+			AccountsPayable::<T>::insert(owner, &info);
 
 			// This pallet controls an amount of funds and transfers them to each of the contributors
 			//TODO: contributors should have the balance locked for tranfers but not for democracy
 			T::RewardCurrency::transfer(
 				&PALLET_ID.into_account_truncating(),
-				&payee,
-				payable_amount,
+				dest,
+				amount,
 				AllowDeath,
 			)?;
-			// Emit event
-			Self::deposit_event(Event::RewardsPaid(payee, payable_amount));
-			Ok(Default::default())
+			Ok(info)
 		}
 
-		/// Update reward address, proving that the caller owns the current native key
-		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::update_reward_address())]
-		pub fn update_reward_address(
-			origin: OriginFor<T>,
-			new_reward_account: T::AccountId,
-		) -> DispatchResultWithPostInfo {
-			let signer = ensure_signed(origin)?;
-
-			// Calculate the veted amount on demand.
-			let info = AccountsPayable::<T>::get(&signer).ok_or(Error::<T>::NoAssociatedClaim)?;
+		/// Consume one of the free claims of who for the current period, if any is left
+		fn use_free_claim(who: &T::AccountId) -> bool {
+			let period = T::FreeClaimPeriod::get();
+			let current_period = if period.is_zero() {
+				Zero::zero()
+			} else {
+				frame_system::Pallet::<T>::block_number() / period
+			};
+			FreeClaimsUsed::<T>::mutate(who, |(claims_period, used)| {
+				if *claims_period != current_period {
+					*claims_period = current_period;
+					*used = 0;
+				}
+				if *used < T::FreeClaimsPerPeriod::get() {
+					*used += 1;
+					true
+				} else {
+					false
+				}
+			})
+		}
 
-			// For now I prefer that we dont support providing an existing account here
-			ensure!(
-				AccountsPayable::<T>::get(&new_reward_account).is_none(),
-				Error::<T>::AlreadyAssociated
-			);
+		/// Ensure new_account has no reward, or has approved merging the reward of previous_account
+		fn ensure_can_receive_reward(
+			new_account: &T::AccountId,
+			previous_account: &T::AccountId,
+		) -> DispatchResult {
+			if AccountsPayable::<T>::contains_key(new_account) {
+				ensure!(
+					new_account != previous_account,
+					Error::<T>::AlreadyAssociated
+				);
+				ensure!(
+					MergeApprovals::<T>::contains_key(new_account, previous_account),
+					Error::<T>::AlreadyAssociated
+				);
+			}
+			Ok(())
+		}
 
-			// Remove previous rewarded account
-			AccountsPayable::<T>::remove(&signer);
+		/// Number of relay addresses handled when moving info to new_account, including the ones
+		/// of the reward it gets merged into. The relay_addresses witness of the call needs to
+		/// cover them, as its weight depends on them
+		fn moved_relay_addresses(
+			info: &RewardInfo<T>,
+			new_account: &T::AccountId,
+			relay_addresses: u32,
+		) -> Result<u32, DispatchError> {
+			let merged_into = AccountsPayable::<T>::get(new_account)
+				.map(|existing| existing.contributed_relay_addresses.len())
+				.unwrap_or_default();
+			let moved = info
+				.contributed_relay_addresses
+				.len()
+				.saturating_add(merged_into) as u32;
+			ensure!(moved <= relay_addresses, Error::<T>::TooFewRelayAddresses);
+			Ok(moved)
+		}
 
-			// Update new rewarded acount
-			AccountsPayable::<T>::insert(&new_reward_account, &info);
+		/// Move the reward of previous_account to new_account, merging it into the reward
+		/// new_account already has, if any
+		fn move_reward_info(
+			previous_account: &T::AccountId,
+			new_account: &T::AccountId,
+			info: RewardInfo<T>,
+		) -> DispatchResult {
+			let info = match AccountsPayable::<T>::get(new_account) {
+				Some(mut existing) => {
+					ensure!(
+						MergeApprovals::<T>::take(new_account, previous_account).is_some(),
+						Error::<T>::AlreadyAssociated
+					);
+					// Both rewards vest on the same schedule, so the sum keeps the claim progress
+					// of each of them
+					existing.total_reward = existing.total_reward.saturating_add(info.total_reward);
+					existing.claimed_reward =
+						existing.claimed_reward.saturating_add(info.claimed_reward);
+					// Slices of a split reward hold parts of the same contributions, which are
+					// added back together
+					for (relay_account, reward) in info.contributed_relay_addresses {
+						match existing
+							.contributed_relay_addresses
+							.iter_mut()
+							.find(|(existing_account, _)| existing_account == &relay_account)
+						{
+							Some((_, existing_reward)) => {
+								*existing_reward =
+									existing_reward
+										.zip(reward)
+										.map(|(existing_reward, reward)| {
+											existing_reward.saturating_add(reward)
+										});
+								Self::drop_relay_account_holder(&relay_account);
+							}
+							None => existing
+								.contributed_relay_addresses
+								.push((relay_account, reward)),
+						}
+					}
 
-			// Emit event
-			Self::deposit_event(Event::RewardAddressUpdated(signer, new_reward_account));
+					Self::deposit_event(Event::RewardsMerged {
+						source: previous_account.clone(),
+						target: new_account.clone(),
+						total_reward: existing.total_reward,
+						claimed_reward: existing.claimed_reward,
+					});
+					existing
+				}
+				None => info,
+			};
 
-			Ok(Default::default())
+			AccountsPayable::<T>::remove(previous_account);
+			AccountsPayable::<T>::insert(new_account, &info);
+			// A change queued for the previous account is no longer relevant
+			PendingRewardAddressChanges::<T>::remove(previous_account);
+			Ok(())
 		}
 
-		/// This extrinsic completes the initialization if some checks are fullfiled. These checks are:
-		///  -The reward contribution money matches the crowdloan pot
-		///  -The end vesting block is higher than the init vesting block
-		///  -The initialization has not complete yet
-		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::complete_initialization())]
-		pub fn complete_initialization(
-			origin: OriginFor<T>,
-			lease_ending_block: T::VestingBlockNumber,
-		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
-
-			let initialized = <Initialized<T>>::get();
-
-			// This ensures there was no prior initialization
+		/// Ensure the reward of who can be moved right away, without going through the delay of
+		/// update_reward_address or racing a change already queued
+		fn ensure_reward_can_move_now(who: &T::AccountId) -> DispatchResult {
 			ensure!(
-				initialized == false,
-				Error::<T>::RewardVecAlreadyInitialized
+				T::RewardAddressChangeDelay::get().is_zero(),
+				Error::<T>::RewardAddressChangeDelayed
 			);
-
-			// This ensures the end vesting block (when all funds are fully vested)
-			// is bigger than the init vesting block
 			ensure!(
-				lease_ending_block > InitVestingBlock::<T>::get(),
-				Error::<T>::VestingPeriodNonValid
+				!PendingRewardAddressChanges::<T>::contains_key(who),
+				Error::<T>::RewardAddressChangePending
 			);
+			Ok(())
+		}
 
-			let current_initialized_rewards = InitializedRewardAmount::<T>::get();
-
-			let reward_difference = Self::pot().saturating_sub(current_initialized_rewards);
+		/// Drop whatever was pending for the reward of account, which no longer exists. Open
+		/// recovery requests are cancelled without slashing the deposit
+		fn clear_reward_account_requests(account: &T::AccountId) {
+			if let Some(pending) = PendingRewardAddressChanges::<T>::take(account) {
+				Self::deposit_event(Event::RewardAddressChangeCancelled {
+					previous_account: account.clone(),
+					new_account: pending.new_account,
+					relay_account: None,
+				});
+			}
+			if let Some(request) = RecoveryRequests::<T>::take(account) {
+				T::RewardCurrency::unreserve(&request.requester, request.deposit);
+				Self::deposit_event(Event::RecoveryCancelled {
+					lost_account: account.clone(),
+					slashed: Zero::zero(),
+				});
+			}
+			// Approvals are only given by the account itself, one approve_merge call each
+			let _ = MergeApprovals::<T>::clear_prefix(account, u32::MAX, None);
+		}
 
-			// Ensure the difference is not bigger than the total number of contributors
-			ensure!(
-				reward_difference < TotalContributors::<T>::get().into(),
-				Error::<T>::RewardsDoNotMatchFund
-			);
+		/// Drop one of the reward entries holding relay_account. Returns whether it was the last
+		/// one, in which case the relay account is no longer claimed by anybody
+		fn drop_relay_account_holder(relay_account: &T::RelayChainAccountId) -> bool {
+			RelayAccountSlices::<T>::mutate_exists(relay_account, |slices| match slices {
+				Some(other_holders) if *other_holders > 1 => {
+					*other_holders -= 1;
+					false
+				}
+				Some(_) => {
+					*slices = None;
+					false
+				}
+				None => true,
+			})
+		}
 
-			// Burn the difference
-			let imbalance = T::RewardCurrency::withdraw(
-				&PALLET_ID.into_account_truncating(),
-				reward_difference,
-				WithdrawReasons::TRANSFER,
-				AllowDeath,
-			)
-			.expect("Shouldnt fail, as the fund should be enough to burn and nothing is locked");
-			drop(imbalance);
+		/// Ensure the call has not been paused by PauseOrigin
+		fn ensure_not_paused(call: PausableCall) -> DispatchResult {
+			ensure!(Paused::<T>::get(call).is_none(), Error::<T>::CallPaused);
+			Ok(())
+		}
 
-			EndVestingBlock::<T>::put(lease_ending_block);
+		/// The reward entry of a contributor, wherever it is stored
+		fn contributor_reward_info(
+			contributor: &ContributorAccount<T::RelayChainAccountId, T::AccountId>,
+		) -> Option<RewardInfo<T>> {
+			match contributor {
+				ContributorAccount::Relay(relay_account) => {
+					UnassociatedContributions::<T>::get(relay_account)
+				}
+				ContributorAccount::Native(native_account) => {
+					AccountsPayable::<T>::get(native_account)
+				}
+			}
+		}
 
-			<Initialized<T>>::put(true);
+		fn insert_contributor_reward_info(
+			contributor: &ContributorAccount<T::RelayChainAccountId, T::AccountId>,
+			reward_info: &RewardInfo<T>,
+		) {
+			match contributor {
+				ContributorAccount::Relay(relay_account) => {
+					UnassociatedContributions::<T>::insert(relay_account, reward_info)
+				}
+				ContributorAccount::Native(native_account) => {
+					AccountsPayable::<T>::insert(native_account, reward_info)
+				}
+			}
+		}
 
-			Ok(Default::default())
+		/// Top up the pot from RewardFundingAccount
+		fn fund_pot(amount: BalanceOf<T>) -> DispatchResult {
+			T::RewardCurrency::transfer(
+				&T::RewardFundingAccount::get(),
+				&Self::account_id(),
+				amount,
+				KeepAlive,
+			)
 		}
 
-		/// Initialize the reward distribution storage. It shortcuts whenever an error is found
+		/// Return funds from the pot to RewardFundingAccount
+		fn refund_from_pot(amount: BalanceOf<T>) -> DispatchResult {
+			T::RewardCurrency::transfer(
+				&Self::account_id(),
+				&T::RewardFundingAccount::get(),
+				amount,
+				AllowDeath,
+			)
+		}
 
-		/// This does not enforce any checks other than making sure we dont go over funds
-		/// complete_initialization should perform any additional
-		#[pallet::call_index(5)]
-		#[pallet::weight(T::WeightInfo::initialize_reward_vec(rewards.len() as u32))]
-		pub fn initialize_reward_vec(
-			origin: OriginFor<T>,
-			rewards: Vec<(T::RelayChainAccountId, Option<T::AccountId>, BalanceOf<T>)>,
-		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
+		/// Checks that need to pass before inserting a batch of rewards
+		fn ensure_reward_vec_can_be_initialized(rows: usize) -> DispatchResult {
 			let initialized = <Initialized<T>>::get();
 			ensure!(
 				initialized == false,
@@ -481,125 +2459,139 @@ pub mod pallet {
 
 			// Ensure we are below the max number of contributors
 			ensure!(
-				rewards.len() as u32 <= T::MaxInitContributors::get(),
+				rows as u32 <= T::MaxInitContributors::get(),
 				Error::<T>::TooManyContributors
 			);
+			Ok(())
+		}
 
-			// What is the amount initialized so far?
-			let mut current_initialized_rewards = InitializedRewardAmount::<T>::get();
-
-			// Total number of contributors
-			let mut total_contributors = TotalContributors::<T>::get();
+		/// Insert a single contribution, making the initial payment if the native account is known.
+		/// Contributions that cannot be inserted are skipped with an event rather than failing, as
+		/// this is supposed to be called with batch calls and we dont want to stall the rest of the
+		/// contributions
+		fn initialize_contribution(
+			relay_account: &T::RelayChainAccountId,
+			native_account: &Option<T::AccountId>,
+			reward: BalanceOf<T>,
+		) -> Result<InitializationOutcome, DispatchError> {
+			if ClaimedRelayChainIds::<T>::get(relay_account).is_some()
+				|| UnassociatedContributions::<T>::get(relay_account).is_some()
+			{
+				Self::deposit_event(Event::InitializedAlreadyInitializedAccount {
+					relay_account: relay_account.clone(),
+					native_account: native_account.clone(),
+					reward,
+				});
+				return Ok(InitializationOutcome::SkippedAlreadyInitialized);
+			}
 
-			let incoming_rewards: BalanceOf<T> = rewards
-				.iter()
-				.fold(0u32.into(), |acc: BalanceOf<T>, (_, _, reward)| {
-					acc + *reward
+			if reward < T::MinimumReward::get() {
+				Self::deposit_event(Event::InitializedAccountWithNotEnoughContribution {
+					relay_account: relay_account.clone(),
+					native_account: native_account.clone(),
+					reward,
 				});
+				return Ok(InitializationOutcome::SkippedNotEnoughContribution);
+			}
 
-			// Ensure we dont go over funds
-			ensure!(
-				current_initialized_rewards + incoming_rewards <= Self::pot(),
-				Error::<T>::BatchBeyondFundPot
-			);
+			// If we have a native_account, we make the payment
+			let initial_payment = if let Some(native_account) = native_account {
+				let first_payment = T::InitializationPayment::get() * reward;
+				T::RewardCurrency::transfer(
+					&PALLET_ID.into_account_truncating(),
+					native_account,
+					first_payment,
+					AllowDeath,
+				)?;
+				Self::deposit_event(Event::InitialPaymentMade {
+					account: native_account.clone(),
+					amount: first_payment,
+				});
+				first_payment
+			} else {
+				0u32.into()
+			};
 
-			for (relay_account, native_account, reward) in &rewards {
-				if ClaimedRelayChainIds::<T>::get(&relay_account).is_some()
-					|| UnassociatedContributions::<T>::get(&relay_account).is_some()
-				{
-					// Dont fail as this is supposed to be called with batch calls and we
-					// dont want to stall the rest of the contributions
-					Self::deposit_event(Event::InitializedAlreadyInitializedAccount(
-						relay_account.clone(),
-						native_account.clone(),
-						*reward,
-					));
-					continue;
-				}
+			// Calculate the reward info to store after the initial payment has been made.
+			let mut reward_info = RewardInfo {
+				total_reward: reward,
+				claimed_reward: initial_payment,
+				contributed_relay_addresses: vec![(relay_account.clone(), Some(reward))],
+			};
 
-				if *reward < T::MinimumReward::get() {
-					// Don't fail as this is supposed to be called with batch calls and we
-					// dont want to stall the rest of the contributions
-					Self::deposit_event(Event::InitializedAccountWithNotEnoughContribution(
-						relay_account.clone(),
-						native_account.clone(),
-						*reward,
-					));
-					continue;
-				}
+			InitializedRewardAmount::<T>::mutate(|amount| *amount += reward - initial_payment);
+			TotalContributors::<T>::mutate(|contributors| *contributors += 1);
 
-				// If we have a native_account, we make the payment
-				let initial_payment = if let Some(native_account) = native_account {
-					let first_payment = T::InitializationPayment::get() * (*reward);
-					T::RewardCurrency::transfer(
-						&PALLET_ID.into_account_truncating(),
-						&native_account,
-						first_payment,
-						AllowDeath,
-					)?;
-					Self::deposit_event(Event::InitialPaymentMade(
-						native_account.clone(),
-						first_payment,
-					));
-					first_payment
+			let outcome = if let Some(native_account) = native_account {
+				let outcome = if let Some(mut inserted_reward_info) =
+					AccountsPayable::<T>::get(native_account)
+				{
+					inserted_reward_info
+						.contributed_relay_addresses
+						.append(&mut reward_info.contributed_relay_addresses);
+					// the native account has already some rewards in, we add the new ones
+					AccountsPayable::<T>::insert(
+						native_account,
+						RewardInfo {
+							total_reward: inserted_reward_info.total_reward
+								+ reward_info.total_reward,
+							claimed_reward: inserted_reward_info.claimed_reward
+								+ reward_info.claimed_reward,
+							contributed_relay_addresses: inserted_reward_info
+								.contributed_relay_addresses,
+						},
+					);
+					InitializationOutcome::MergedIntoExistingAccount
 				} else {
-					0u32.into()
-				};
-
-				// Calculate the reward info to store after the initial payment has been made.
-				let mut reward_info = RewardInfo {
-					total_reward: *reward,
-					claimed_reward: initial_payment,
-					contributed_relay_addresses: vec![relay_account.clone()],
+					// First reward association
+					AccountsPayable::<T>::insert(native_account, reward_info);
+					InitializationOutcome::Inserted
 				};
+				ClaimedRelayChainIds::<T>::insert(relay_account, ());
+				outcome
+			} else {
+				UnassociatedContributions::<T>::insert(relay_account, reward_info);
+				InitializationOutcome::Inserted
+			};
 
-				current_initialized_rewards += *reward - initial_payment;
-				total_contributors += 1;
+			Ok(outcome)
+		}
 
-				if let Some(native_account) = native_account {
-					if let Some(mut inserted_reward_info) =
-						AccountsPayable::<T>::get(native_account)
-					{
-						inserted_reward_info
-							.contributed_relay_addresses
-							.append(&mut reward_info.contributed_relay_addresses);
-						// the native account has already some rewards in, we add the new ones
-						AccountsPayable::<T>::insert(
-							native_account,
-							RewardInfo {
-								total_reward: inserted_reward_info.total_reward
-									+ reward_info.total_reward,
-								claimed_reward: inserted_reward_info.claimed_reward
-									+ reward_info.claimed_reward,
-								contributed_relay_addresses: inserted_reward_info
-									.contributed_relay_addresses,
-							},
-						);
+		/// Share of the votes of reward_info cast by the voted relay accounts.
+		/// Weighted votes fall back to one vote per account if nothing was contributed at all, or
+		/// if the contribution of any of the relay accounts is unknown
+		fn relay_vote_share(
+			voted: &BTreeMap<T::RelayChainAccountId, ()>,
+			reward_info: &RewardInfo<T>,
+		) -> Perbill {
+			let per_account = Perbill::from_rational(
+				voted.len() as u32,
+				reward_info.contributed_relay_addresses.len() as u32,
+			);
+			match T::RewardAddressRelayVoteMode::get() {
+				RelayVoteMode::PerAccount => per_account,
+				RelayVoteMode::ContributionWeighted => {
+					let mut voted_reward = BalanceOf::<T>::zero();
+					let mut total_reward = BalanceOf::<T>::zero();
+					for (relay_account, reward) in &reward_info.contributed_relay_addresses {
+						let reward = match reward {
+							Some(reward) => *reward,
+							None => return per_account,
+						};
+						if voted.contains_key(relay_account) {
+							voted_reward = voted_reward.saturating_add(reward);
+						}
+						total_reward = total_reward.saturating_add(reward);
+					}
+					if total_reward.is_zero() {
+						per_account
 					} else {
-						// First reward association
-						AccountsPayable::<T>::insert(native_account, reward_info);
+						Perbill::from_rational(voted_reward, total_reward)
 					}
-					ClaimedRelayChainIds::<T>::insert(relay_account, ());
-				} else {
-					UnassociatedContributions::<T>::insert(relay_account, reward_info);
 				}
 			}
-			InitializedRewardAmount::<T>::put(current_initialized_rewards);
-			TotalContributors::<T>::put(total_contributors);
-
-			Ok(Default::default())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		/// The account ID that holds the Crowdloan's funds
-		pub fn account_id() -> T::AccountId {
-			PALLET_ID.into_account_truncating()
-		}
-		/// The Account Id's balance
-		pub fn pot() -> BalanceOf<T> {
-			T::RewardCurrency::free_balance(&Self::account_id())
-		}
 		/// Verify a set of signatures made with relay chain accounts
 		/// We are verifying all the signatures, and then counting
 		/// We could do something more efficient like count as we verify
@@ -609,7 +2601,7 @@ pub mod pallet {
 			proofs: Vec<(T::RelayChainAccountId, MultiSignature)>,
 			reward_info: RewardInfo<T>,
 			payload: Vec<u8>,
-		) -> DispatchResult {
+		) -> Result<Vec<T::RelayChainAccountId>, DispatchError> {
 			// The proofs should
 			// 1. be signed by contributors to this address, otherwise they are not counted
 			// 2. Signs a valid native identity
@@ -624,7 +2616,8 @@ pub mod pallet {
 					ensure!(
 						reward_info
 							.contributed_relay_addresses
-							.contains(&relay_account),
+							.iter()
+							.any(|(contributor, _)| contributor == &relay_account),
 						Error::<T>::NonContributedAddressProvided
 					);
 
@@ -638,15 +2631,16 @@ pub mod pallet {
 				}
 			}
 
-			// Ensure the votes are sufficient
+			// Ensure the votes are sufficient. Rewards received through transfer_position might have
+			// no relay addresses at all, and these can not be changed with relay keys
 			ensure!(
-				Perbill::from_rational(
-					voted.len() as u32,
-					reward_info.contributed_relay_addresses.len() as u32
-				) >= T::RewardAddressRelayVoteThreshold::get(),
+				!voted.is_empty()
+					&& Self::relay_vote_share(&voted, &reward_info)
+						>= T::RewardAddressRelayVoteThreshold::get(),
 				Error::<T>::InsufficientNumberOfValidProofs
 			);
-			Ok(())
+			// Duplicated proofs are skipped, so this might be less than the proofs received
+			Ok(voted.into_keys().collect())
 		}
 	}
 
@@ -687,6 +2681,67 @@ pub mod pallet {
 		NonContributedAddressProvided,
 		/// User submitted an unsifficient number of proofs to change the reward address
 		InsufficientNumberOfValidProofs,
+		/// The reward cannot be set below what the contributor already claimed
+		RewardBelowClaimedAmount,
+		/// The call is paused
+		CallPaused,
+		/// The call is already paused
+		CallAlreadyPaused,
+		/// The call is not paused
+		CallNotPaused,
+		/// claim_many received too many accounts
+		TooManyClaims,
+		/// The requested amount is bigger than the vested reward not claimed yet
+		ClaimAmountExceedsVestedReward,
+		/// The amount to stake is bigger than the reward not claimed yet
+		StakeAmountExceedsUnclaimedReward,
+		/// The amount to unbond is bigger than the staked reward vested so far
+		UnbondAmountExceedsVestedStake,
+		/// The reward address cannot change or be removed while it has staked unvested rewards
+		RewardsStaked,
+		/// An account cannot merge its reward into itself
+		CannotMergeIntoItself,
+		/// The merge was not approved by the target account
+		MergeNotApproved,
+		/// split_reward received too many destinations
+		TooManySplits,
+		/// The shares do not add up to 100%, a destination is repeated or would get no reward
+		InvalidSplit,
+		/// Transferring reward positions is not enabled
+		TransfersDisabled,
+		/// The share to transfer is zero or the destination is the caller
+		InvalidTransfer,
+		/// A reward address change is already queued for the account
+		RewardAddressChangePending,
+		/// There is no reward address change queued for the account
+		NoPendingRewardAddressChange,
+		/// The delay of the queued reward address change is not over yet
+		RewardAddressChangeNotExecutableYet,
+		/// A recovery was already requested for the account
+		RecoveryAlreadyRequested,
+		/// There is no recovery requested for the account
+		NoRecoveryRequest,
+		/// The recovery was already approved
+		RecoveryAlreadyApproved,
+		/// The recovery was not approved by AdminOrigin
+		RecoveryNotApproved,
+		/// The delay of the approved recovery is not over yet
+		RecoveryNotExecutableYet,
+		/// The signatories and threshold are not valid or do not derive the relay account
+		InvalidMultisig,
+		/// The delegation is no longer valid
+		DelegationExpired,
+		/// The delegation was not signed by the contributing relay account
+		InvalidDelegationSignature,
+		/// The reward account did not consent to receive the reward
+		AssociationNotApproved,
+		/// The rewards involved have more relay addresses than the ones given
+		TooFewRelayAddresses,
+		/// Reward address changes are delayed, so the reward can only be moved with
+		/// update_reward_address
+		RewardAddressChangeDelayed,
+		/// The consent was not signed by the reward account
+		InvalidConsentSignature,
 	}
 
 	#[pallet::genesis_config]
@@ -751,30 +2806,271 @@ pub mod pallet {
 	/// Total number of contributors to aid hinting benchmarking
 	type TotalContributors<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn paused)]
+	/// Calls that have been paused by PauseOrigin
+	pub type Paused<T: Config> = StorageMap<_, Blake2_128Concat, PausableCall, ()>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auto_payout_cursor)]
+	/// Raw storage key of the last AccountsPayable entry visited by the auto payouts
+	pub type AutoPayoutCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn free_claims_used)]
+	/// Free claims used by each account, together with the period in which they were used
+	pub type FreeClaimsUsed<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (BlockNumberFor<T>, u32), ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn merge_approvals)]
+	/// Merges approved by the target account (first key) from the source account (second key)
+	pub type MergeApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn staked_unvested_rewards)]
+	/// Rewards bonded through RewardStaking that have not been unbonded yet
+	pub type StakedUnvestedRewards<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn transfers_enabled)]
+	/// Whether AdminOrigin enabled transfer_position
+	pub type TransfersEnabled<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn recovery_requests)]
+	/// Recoveries requested for accounts whose native key was lost
+	pub type RecoveryRequests<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		RecoveryRequest<T::AccountId, BalanceOf<T>, T::Hash, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_reward_address_changes)]
+	/// Reward address changes queued by update_reward_address, by current reward account
+	pub type PendingRewardAddressChanges<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		PendingRewardAddressChange<T::AccountId, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn relay_account_slices)]
+	/// Number of reward entries holding each relay account besides the first one, after a
+	/// split_reward. Relay accounts held by a single entry are not stored
+	pub type RelayAccountSlices<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::RelayChainAccountId, u32, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn association_approvals)]
+	/// Associations of relay accounts (second key) approved by the reward account (first key)
+	pub type AssociationApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::RelayChainAccountId,
+		(),
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// The initial payment of InitializationPayment % was paid
-		InitialPaymentMade(T::AccountId, BalanceOf<T>),
+		InitialPaymentMade {
+			account: T::AccountId,
+			amount: BalanceOf<T>,
+		},
 		/// Someone has proven they made a contribution and associated a native identity with it.
-		/// Data is the relay account,  native account and the total amount of _rewards_ that will be paid
-		NativeIdentityAssociated(T::RelayChainAccountId, T::AccountId, BalanceOf<T>),
+		/// total_reward is the total amount of _rewards_ that will be paid
+		NativeIdentityAssociated {
+			relay_account: T::RelayChainAccountId,
+			account: T::AccountId,
+			total_reward: BalanceOf<T>,
+		},
 		/// A contributor has claimed some rewards.
-		/// Data is the account getting paid and the amount of rewards paid.
-		RewardsPaid(T::AccountId, BalanceOf<T>),
+		/// Also reports what was claimed in total so far and what is still unvested at
+		/// vesting_block
+		RewardsPaid {
+			account: T::AccountId,
+			amount: BalanceOf<T>,
+			total_claimed: BalanceOf<T>,
+			remaining_unvested: BalanceOf<T>,
+			vesting_block: T::VestingBlockNumber,
+		},
 		/// A contributor has updated the reward address.
-		RewardAddressUpdated(T::AccountId, T::AccountId),
+		RewardAddressUpdated {
+			previous_account: T::AccountId,
+			new_account: T::AccountId,
+			mechanism: RewardAddressChangeMechanism<T::RelayChainAccountId>,
+		},
 		/// When initializing the reward vec an already initialized account was found
-		InitializedAlreadyInitializedAccount(
-			T::RelayChainAccountId,
-			Option<T::AccountId>,
-			BalanceOf<T>,
-		),
+		InitializedAlreadyInitializedAccount {
+			relay_account: T::RelayChainAccountId,
+			native_account: Option<T::AccountId>,
+			reward: BalanceOf<T>,
+		},
 		/// When initializing the reward vec an already initialized account was found
-		InitializedAccountWithNotEnoughContribution(
-			T::RelayChainAccountId,
-			Option<T::AccountId>,
-			BalanceOf<T>,
-		),
+		InitializedAccountWithNotEnoughContribution {
+			relay_account: T::RelayChainAccountId,
+			native_account: Option<T::AccountId>,
+			reward: BalanceOf<T>,
+		},
+		/// The initialization was completed, rewards start vesting.
+		/// initialized_reward_amount excludes the initial payments already made
+		InitializationCompleted {
+			end_vesting_block: T::VestingBlockNumber,
+			total_contributors: u32,
+			initialized_reward_amount: BalanceOf<T>,
+		},
+		/// The total reward of a contributor was adjusted.
+		RewardAdjusted {
+			contributor: ContributorAccount<T::RelayChainAccountId, T::AccountId>,
+			previous_reward: BalanceOf<T>,
+			new_reward: BalanceOf<T>,
+		},
+		/// A contribution left out of the initialization was added.
+		LateContributorAdded {
+			relay_account: T::RelayChainAccountId,
+			native_account: Option<T::AccountId>,
+			reward: BalanceOf<T>,
+		},
+		/// A contributor was removed, returning the unclaimed reward to RewardFundingAccount
+		ContributorRemoved {
+			contributor: ContributorAccount<T::RelayChainAccountId, T::AccountId>,
+			refunded: BalanceOf<T>,
+		},
+		/// A call was paused
+		CallPaused { call: PausableCall },
+		/// A call was unpaused
+		CallUnpaused { call: PausableCall },
+		/// Rewards were paid to several contributors at once.
+		RewardsBatchPaid {
+			accounts_paid: u32,
+			total_paid: BalanceOf<T>,
+		},
+		/// A contributor has claimed some rewards into another account.
+		/// Also reports what was claimed in total so far and what is still unvested at
+		/// vesting_block
+		RewardsPaidTo {
+			account: T::AccountId,
+			dest: T::AccountId,
+			amount: BalanceOf<T>,
+			total_claimed: BalanceOf<T>,
+			remaining_unvested: BalanceOf<T>,
+			vesting_block: T::VestingBlockNumber,
+		},
+		/// Claimed rewards were forwarded by the OnRewardClaimed hook.
+		RewardsForwarded {
+			account: T::AccountId,
+			amount: BalanceOf<T>,
+			target: T::RewardClaimTarget,
+		},
+		/// Unvested rewards were staked.
+		UnvestedRewardsStaked {
+			account: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Staked rewards were unbonded.
+		StakedRewardsUnbonded {
+			account: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// target accepts that the reward of source is merged into its own
+		MergeApproved {
+			target: T::AccountId,
+			source: T::AccountId,
+		},
+		/// target no longer accepts that the reward of source is merged into its own
+		MergeApprovalCancelled {
+			target: T::AccountId,
+			source: T::AccountId,
+		},
+		/// The reward of source was merged into the reward of target.
+		/// Reports the resulting totals of target
+		RewardsMerged {
+			source: T::AccountId,
+			target: T::AccountId,
+			total_reward: BalanceOf<T>,
+			claimed_reward: BalanceOf<T>,
+		},
+		/// A slice of the reward of source was given to destination
+		RewardSplit {
+			source: T::AccountId,
+			destination: T::AccountId,
+			total_reward: BalanceOf<T>,
+			claimed_reward: BalanceOf<T>,
+		},
+		/// A reward address change was queued, and can be executed from executable_at
+		RewardAddressChangeQueued {
+			previous_account: T::AccountId,
+			new_account: T::AccountId,
+			executable_at: BlockNumberFor<T>,
+		},
+		/// A queued reward address change was cancelled, by the owner or by relay_account
+		RewardAddressChangeCancelled {
+			previous_account: T::AccountId,
+			new_account: T::AccountId,
+			relay_account: Option<T::RelayChainAccountId>,
+		},
+		/// The recovery of an account whose native key was lost was requested
+		RecoveryRequested {
+			lost_account: T::AccountId,
+			new_account: T::AccountId,
+			requester: T::AccountId,
+			evidence_hash: T::Hash,
+			deposit: BalanceOf<T>,
+		},
+		/// A recovery was approved, and can be finalized from executable_at
+		RecoveryApproved {
+			lost_account: T::AccountId,
+			executable_at: BlockNumberFor<T>,
+		},
+		/// A recovery was rejected, vetoed or withdrawn, slashing part of the deposit
+		RecoveryCancelled {
+			lost_account: T::AccountId,
+			slashed: BalanceOf<T>,
+		},
+		/// The reward of a lost account was recovered into new_account
+		RecoveryFinalized {
+			lost_account: T::AccountId,
+			new_account: T::AccountId,
+			evidence_hash: T::Hash,
+		},
+		/// transfer_position was enabled or disabled
+		TransfersEnabledSet { enabled: bool },
+		/// Part of the reward of source was transferred to dest.
+		/// Reports the transferred totals
+		PositionTransferred {
+			source: T::AccountId,
+			dest: T::AccountId,
+			total_reward: BalanceOf<T>,
+			claimed_reward: BalanceOf<T>,
+		},
+		/// account accepts that the contribution of relay_account is associated with it
+		AssociationApproved {
+			account: T::AccountId,
+			relay_account: T::RelayChainAccountId,
+		},
+		/// account no longer accepts that the contribution of relay_account is associated with it
+		AssociationApprovalCancelled {
+			account: T::AccountId,
+			relay_account: T::RelayChainAccountId,
+		},
 	}
 }
@@ -0,0 +1,91 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations of the crowdloan rewards pallet
+
+use crate::{AccountsPayable, BalanceOf, Config, Pallet, RewardInfo, UnassociatedContributions};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Reward info as stored before version 1, without the reward of each relay account
+#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq)]
+pub struct OldRewardInfo<Balance, RelayChainAccountId> {
+	pub total_reward: Balance,
+	pub claimed_reward: Balance,
+	pub contributed_relay_addresses: Vec<RelayChainAccountId>,
+}
+
+/// Stores the reward of each relay account inside the reward info.
+///
+/// Entries with a single relay account get the whole total reward. The split of entries that
+/// merged several contributions was never stored, so the reward of their relay accounts is marked
+/// as unknown, and votes over them are counted per account
+pub struct MigrateToV1<T>(PhantomData<T>);
+
+impl<T: Config> MigrateToV1<T> {
+	fn migrate_reward_info(
+		old: OldRewardInfo<BalanceOf<T>, T::RelayChainAccountId>,
+	) -> RewardInfo<T> {
+		let known_reward = if old.contributed_relay_addresses.len() == 1 {
+			Some(old.total_reward)
+		} else {
+			None
+		};
+		let contributed_relay_addresses = old
+			.contributed_relay_addresses
+			.into_iter()
+			.map(|relay_account| (relay_account, known_reward))
+			.collect();
+
+		RewardInfo {
+			total_reward: old.total_reward,
+			claimed_reward: old.claimed_reward,
+			contributed_relay_addresses,
+		}
+	}
+}
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			log::info!(target: "crowdloan-rewards", "MigrateToV1 already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut translated = 0u64;
+		AccountsPayable::<T>::translate::<OldRewardInfo<BalanceOf<T>, T::RelayChainAccountId>, _>(
+			|_, old| {
+				translated += 1;
+				Some(Self::migrate_reward_info(old))
+			},
+		);
+		UnassociatedContributions::<T>::translate::<
+			OldRewardInfo<BalanceOf<T>, T::RelayChainAccountId>,
+			_,
+		>(|_, old| {
+			translated += 1;
+			Some(Self::migrate_reward_info(old))
+		});
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+		log::info!(target: "crowdloan-rewards", "MigrateToV1 migrated {} reward entries", translated);
+
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
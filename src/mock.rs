@@ -15,17 +15,26 @@
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
 //! Test utilities
-use crate::{self as pallet_crowdloan_rewards, Config};
+use crate::{
+	self as pallet_crowdloan_rewards,
+	traits::{OnRewardClaimed, RewardStaking},
+	Config, RelayVoteMode,
+};
 use frame_support::{
-	construct_runtime, parameter_types,
-	traits::{ConstU32, GenesisBuild, Nothing, OnFinalize, OnInitialize},
+	construct_runtime,
+	dispatch::{DispatchError, DispatchResult},
+	ensure, ord_parameter_types, parameter_types,
+	traits::{
+		ConstU32, EitherOfDiverse, GenesisBuild, Get, Nothing, OnFinalize, OnInitialize,
+		ReservableCurrency,
+	},
 };
-use frame_system::EnsureSigned;
+use frame_system::{EnsureRoot, EnsureSigned, EnsureSignedBy};
 use sp_core::{ed25519, Pair, H256};
 use sp_io;
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	testing::{Header, TestSignature, UintAuthorityId},
+	traits::{BlakeTwo256, IdentityLookup, Zero},
 	Perbill,
 };
 use sp_std::convert::{From, TryInto};
@@ -113,22 +122,58 @@ impl sp_runtime::traits::BlockNumberProvider for MockedBlockProvider {
 
 parameter_types! {
 	pub const TestMaxInitContributors: u32 = 8;
+	pub const TestMaxClaimBatch: u32 = 4;
+	pub const TestMaxRewardSplits: u32 = 3;
+	pub const TestMaxMultisigSignatories: u32 = 4;
+	pub static TestMaxAutoPayoutsPerBlock: u32 = 0;
+	pub static TestMinimumAutoPayout: u128 = 0;
+	pub const TestFreeClaimsPerPeriod: u32 = 2;
+	pub const TestFreeClaimPeriod: u64 = 10;
+	pub static TestRewardAddressChangeDelay: u64 = 0;
+	pub const TestRecoveryDeposit: u128 = 10;
+	pub const TestRecoveryDelay: u64 = 5;
+	pub const TestRewardFundingAccount: u64 = 99;
+	pub static ForwardedRewards: Vec<(u64, Balance, u8)> = vec![];
 	pub const TestMinimumReward: u128 = 0;
 	pub const TestInitialized: bool = false;
 	pub const TestInitializationPayment: Perbill = Perbill::from_percent(20);
 	pub const TestRewardAddressRelayVoteThreshold: Perbill = Perbill::from_percent(50);
+	pub static TestRewardAddressRelayVoteMode: RelayVoteMode = RelayVoteMode::PerAccount;
+	pub static TestRequireNativeConsent: bool = false;
 	pub const TestSigantureNetworkIdentifier: &'static [u8] = b"test-";
 }
 
+ord_parameter_types! {
+	pub const Initializer: u64 = 100;
+}
+
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Initialized = TestInitialized;
 	type InitializationPayment = TestInitializationPayment;
 	type MaxInitContributors = TestMaxInitContributors;
+	type MaxClaimBatch = TestMaxClaimBatch;
+	type MaxRewardSplits = TestMaxRewardSplits;
+	type MaxMultisigSignatories = TestMaxMultisigSignatories;
+	type MaxAutoPayoutsPerBlock = TestMaxAutoPayoutsPerBlock;
+	type MinimumAutoPayout = TestMinimumAutoPayout;
+	type FreeClaimsPerPeriod = TestFreeClaimsPerPeriod;
+	type FreeClaimPeriod = TestFreeClaimPeriod;
+	type RewardAddressChangeDelay = TestRewardAddressChangeDelay;
+	type RecoveryDeposit = TestRecoveryDeposit;
+	type RecoveryDelay = TestRecoveryDelay;
 	type MinimumReward = TestMinimumReward;
 	type RewardCurrency = Balances;
+	type RewardFundingAccount = TestRewardFundingAccount;
 	type RelayChainAccountId = [u8; 32];
+	type InitializationOrigin = EitherOfDiverse<EnsureRoot<u64>, EnsureSignedBy<Initializer, u64>>;
+	type AdminOrigin = EnsureRoot<u64>;
+	type PauseOrigin = EnsureRoot<u64>;
 	type RewardAddressRelayVoteThreshold = TestRewardAddressRelayVoteThreshold;
+	type RewardAddressRelayVoteMode = TestRewardAddressRelayVoteMode;
+	type RequireNativeConsent = TestRequireNativeConsent;
+	type NativeSignature = TestSignature;
+	type NativeSigner = UintAuthorityId;
 	// The origin that is allowed to associate the reward
 	type RewardAddressAssociateOrigin = EnsureSigned<Self::AccountId>;
 	// The origin that is allowed to change the reward
@@ -137,9 +182,44 @@ impl Config for Test {
 
 	type VestingBlockNumber = u64;
 	type VestingBlockProvider = MockedBlockProvider;
+	type RewardClaimTarget = u8;
+	type OnRewardClaimed = MockRewardForwarder;
+	type RewardStaking = MockRewardStaking;
 	type WeightInfo = ();
 }
 
+/// Reserves the forwarded rewards as if they had been staked, rejecting target 0
+pub struct MockRewardForwarder;
+impl OnRewardClaimed<u64, Balance, u8> for MockRewardForwarder {
+	fn on_reward_claimed(who: &u64, amount: Balance, target: u8) -> DispatchResult {
+		ensure!(
+			target != 0,
+			DispatchError::Other("Target rejects the rewards")
+		);
+		Balances::reserve(who, amount)?;
+		let mut forwarded = ForwardedRewards::get();
+		forwarded.push((*who, amount, target));
+		ForwardedRewards::set(forwarded);
+		Ok(())
+	}
+}
+
+/// Bonds by reserving the funds
+pub struct MockRewardStaking;
+impl RewardStaking<u64, Balance> for MockRewardStaking {
+	fn bond(who: &u64, amount: Balance) -> DispatchResult {
+		Balances::reserve(who, amount)
+	}
+
+	fn unbond(who: &u64, amount: Balance) -> DispatchResult {
+		ensure!(
+			Balances::unreserve(who, amount).is_zero(),
+			DispatchError::Other("Not enough bonded")
+		);
+		Ok(())
+	}
+}
+
 impl pallet_utility::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -151,6 +231,11 @@ fn genesis(funded_amount: Balance) -> sp_io::TestExternalities {
 	let mut storage = frame_system::GenesisConfig::default()
 		.build_storage::<Test>()
 		.unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(TestRewardFundingAccount::get(), 10_000)],
+	}
+	.assimilate_storage(&mut storage)
+	.expect("Pallet balances storage can be assimilated");
 	pallet_crowdloan_rewards::GenesisConfig::<Test> { funded_amount }
 		.assimilate_storage(&mut storage)
 		.expect("Pallet balances storage can be assimilated");
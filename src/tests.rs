@@ -16,13 +16,19 @@
 
 //! Unit testing
 
+use crate::migrations::{MigrateToV1, OldRewardInfo};
+use crate::weights::WeightInfo;
 use crate::*;
-use frame_support::dispatch::{DispatchError, Dispatchable};
+use frame_support::dispatch::{DispatchError, Dispatchable, Pays};
+use frame_support::traits::{Get, GetStorageVersion, OnIdle, OnRuntimeUpgrade, StorageVersion};
+use frame_support::weights::Weight;
 use frame_support::{assert_noop, assert_ok};
 use mock::*;
 use parity_scale_codec::Encode;
 use sp_core::Pair;
-use sp_runtime::{ModuleError, MultiSignature};
+use sp_runtime::{
+	testing::TestSignature, traits::BlockNumberProvider, ModuleError, MultiSignature, Perbill,
+};
 
 // Constant that reflects the desired vesting period for the tests
 // Most tests complete initialization passing initRelayBlock + VESTING as the endRelayBlock
@@ -107,7 +113,7 @@ fn proving_assignation_works() {
 			Crowdloan::accounts_payable(&1)
 				.unwrap()
 				.contributed_relay_addresses,
-			vec![[1u8; 32]]
+			vec![([1u8; 32], Some(500))]
 		);
 
 		roll_to(4);
@@ -117,7 +123,8 @@ fn proving_assignation_works() {
 				RuntimeOrigin::signed(4),
 				4,
 				pairs[0].public().into(),
-				signature.clone()
+				signature.clone(),
+				None
 			),
 			Error::<Test>::InvalidClaimSignature
 		);
@@ -128,7 +135,8 @@ fn proving_assignation_works() {
 				RuntimeOrigin::signed(4),
 				1,
 				pairs[0].public().into(),
-				alread_associated_signature
+				alread_associated_signature,
+				None
 			),
 			Error::<Test>::AlreadyAssociated
 		);
@@ -138,7 +146,8 @@ fn proving_assignation_works() {
 			RuntimeOrigin::signed(4),
 			3,
 			pairs[0].public().into(),
-			signature.clone()
+			signature.clone(),
+			None
 		));
 
 		// Signature is right, but relay address is no longer on unassociated
@@ -147,7 +156,8 @@ fn proving_assignation_works() {
 				RuntimeOrigin::signed(4),
 				3,
 				pairs[0].public().into(),
-				signature
+				signature,
+				None
 			),
 			Error::<Test>::NoAssociatedClaim
 		);
@@ -158,17 +168,35 @@ fn proving_assignation_works() {
 			Crowdloan::accounts_payable(&3)
 				.unwrap()
 				.contributed_relay_addresses,
-			vec![*pairs[0].public().as_array_ref()]
+			vec![(*pairs[0].public().as_array_ref(), Some(500))]
 		);
 
 		assert!(Crowdloan::unassociated_contributions(pairs[0].public().as_array_ref()).is_none());
 		assert!(Crowdloan::claimed_relay_chain_ids(pairs[0].public().as_array_ref()).is_some());
 
 		let expected = vec![
-			crate::Event::InitialPaymentMade(1, 100),
-			crate::Event::InitialPaymentMade(2, 100),
-			crate::Event::InitialPaymentMade(3, 100),
-			crate::Event::NativeIdentityAssociated(pairs[0].public().into(), 3, 500),
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 8,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 3,
+				amount: 100,
+			},
+			crate::Event::NativeIdentityAssociated {
+				relay_account: pairs[0].public().into(),
+				account: 3,
+				total_reward: 500,
+			},
 		];
 		assert_eq!(events(), expected);
 	});
@@ -202,7 +230,7 @@ fn initializing_multi_relay_to_single_native_address_works() {
 			Crowdloan::accounts_payable(&1)
 				.unwrap()
 				.contributed_relay_addresses,
-			vec![[1u8; 32], [2u8; 32]]
+			vec![([1u8; 32], Some(500)), ([2u8; 32], Some(500))]
 		);
 
 		roll_to(4);
@@ -214,9 +242,26 @@ fn initializing_multi_relay_to_single_native_address_works() {
 		);
 
 		let expected = vec![
-			crate::Event::InitialPaymentMade(1, 100),
-			crate::Event::InitialPaymentMade(1, 100),
-			crate::Event::RewardsPaid(1, 200),
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 200,
+				total_claimed: 400,
+				remaining_unvested: 600,
+				vesting_block: 4,
+			},
 		];
 		assert_eq!(events(), expected);
 	});
@@ -278,15 +323,68 @@ fn paying_works_step_by_step() {
 		);
 
 		let expected = vec![
-			crate::Event::InitialPaymentMade(1, 100),
-			crate::Event::InitialPaymentMade(2, 100),
-			crate::Event::RewardsPaid(1, 100),
-			crate::Event::RewardsPaid(1, 50),
-			crate::Event::RewardsPaid(1, 50),
-			crate::Event::RewardsPaid(1, 50),
-			crate::Event::RewardsPaid(1, 50),
-			crate::Event::RewardsPaid(1, 50),
-			crate::Event::RewardsPaid(1, 50),
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 100,
+				total_claimed: 200,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 50,
+				total_claimed: 250,
+				remaining_unvested: 250,
+				vesting_block: 5,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 50,
+				total_claimed: 300,
+				remaining_unvested: 200,
+				vesting_block: 6,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 50,
+				total_claimed: 350,
+				remaining_unvested: 150,
+				vesting_block: 7,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 50,
+				total_claimed: 400,
+				remaining_unvested: 100,
+				vesting_block: 8,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 50,
+				total_claimed: 450,
+				remaining_unvested: 50,
+				vesting_block: 9,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 50,
+				total_claimed: 500,
+				remaining_unvested: 0,
+				vesting_block: 10,
+			},
 		];
 		assert_eq!(events(), expected);
 	});
@@ -343,13 +441,54 @@ fn paying_works_after_unclaimed_period() {
 		);
 
 		let expected = vec![
-			crate::Event::InitialPaymentMade(1, 100),
-			crate::Event::InitialPaymentMade(2, 100),
-			crate::Event::RewardsPaid(1, 100),
-			crate::Event::RewardsPaid(1, 50),
-			crate::Event::RewardsPaid(1, 50),
-			crate::Event::RewardsPaid(1, 50),
-			crate::Event::RewardsPaid(1, 150),
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 100,
+				total_claimed: 200,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 50,
+				total_claimed: 250,
+				remaining_unvested: 250,
+				vesting_block: 5,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 50,
+				total_claimed: 300,
+				remaining_unvested: 200,
+				vesting_block: 6,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 50,
+				total_claimed: 350,
+				remaining_unvested: 150,
+				vesting_block: 7,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 150,
+				total_claimed: 500,
+				remaining_unvested: 0,
+				vesting_block: 11,
+			},
 		];
 		assert_eq!(events(), expected);
 	});
@@ -387,16 +526,41 @@ fn paying_late_joiner_works() {
 			RuntimeOrigin::signed(4),
 			3,
 			pairs[0].public().into(),
-			signature.clone()
+			signature.clone(),
+			None
 		));
 		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(3)));
 		assert_eq!(Crowdloan::accounts_payable(&3).unwrap().claimed_reward, 500);
 		let expected = vec![
-			crate::Event::InitialPaymentMade(1, 100),
-			crate::Event::InitialPaymentMade(2, 100),
-			crate::Event::InitialPaymentMade(3, 100),
-			crate::Event::NativeIdentityAssociated(pairs[0].public().into(), 3, 500),
-			crate::Event::RewardsPaid(3, 400),
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 8,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 3,
+				amount: 100,
+			},
+			crate::Event::NativeIdentityAssociated {
+				relay_account: pairs[0].public().into(),
+				account: 3,
+				total_reward: 500,
+			},
+			crate::Event::RewardsPaid {
+				account: 3,
+				amount: 400,
+				total_claimed: 500,
+				remaining_unvested: 0,
+				vesting_block: 12,
+			},
 		];
 		assert_eq!(events(), expected);
 	});
@@ -433,7 +597,8 @@ fn update_address_works() {
 		);
 		assert_ok!(Crowdloan::update_reward_address(
 			RuntimeOrigin::signed(1),
-			8
+			8,
+			1
 		));
 		assert_eq!(Crowdloan::accounts_payable(&8).unwrap().claimed_reward, 200);
 		roll_to(6);
@@ -441,11 +606,38 @@ fn update_address_works() {
 		assert_eq!(Crowdloan::accounts_payable(&8).unwrap().claimed_reward, 300);
 		// The initial payment is not
 		let expected = vec![
-			crate::Event::InitialPaymentMade(1, 100),
-			crate::Event::InitialPaymentMade(2, 100),
-			crate::Event::RewardsPaid(1, 100),
-			crate::Event::RewardAddressUpdated(1, 8),
-			crate::Event::RewardsPaid(8, 100),
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 100,
+				total_claimed: 200,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+			crate::Event::RewardAddressUpdated {
+				previous_account: 1,
+				new_account: 8,
+				mechanism: RewardAddressChangeMechanism::SelfUpdate,
+			},
+			crate::Event::RewardsPaid {
+				account: 8,
+				amount: 100,
+				total_claimed: 300,
+				remaining_unvested: 200,
+				vesting_block: 6,
+			},
 		];
 		assert_eq!(events(), expected);
 	});
@@ -478,7 +670,7 @@ fn update_address_with_existing_address_fails() {
 		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(1)));
 		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(2)));
 		assert_noop!(
-			Crowdloan::update_reward_address(RuntimeOrigin::signed(1), 2),
+			Crowdloan::update_reward_address(RuntimeOrigin::signed(1), 2, 2),
 			Error::<Test>::AlreadyAssociated
 		);
 	});
@@ -513,6 +705,7 @@ fn update_address_with_existing_with_multi_address_works() {
 		// We make sure all rewards go to the new address
 		assert_ok!(Crowdloan::update_reward_address(
 			RuntimeOrigin::signed(1),
+			2,
 			2
 		));
 		assert_eq!(Crowdloan::accounts_payable(&2).unwrap().claimed_reward, 400);
@@ -738,12 +931,44 @@ fn floating_point_arithmetic_works() {
 			75u128
 		);
 		let expected = vec![
-			crate::Event::InitialPaymentMade(1, 238),
-			crate::Event::InitialPaymentMade(2, 237),
-			crate::Event::InitialPaymentMade(3, 25),
-			crate::Event::RewardsPaid(3, 25),
-			crate::Event::RewardsPaid(3, 12),
-			crate::Event::RewardsPaid(3, 13),
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 238,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 237,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 3,
+				amount: 25,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 3,
+				initialized_reward_amount: 2000,
+			},
+			crate::Event::RewardsPaid {
+				account: 3,
+				amount: 25,
+				total_claimed: 50,
+				remaining_unvested: 75,
+				vesting_block: 4,
+			},
+			crate::Event::RewardsPaid {
+				account: 3,
+				amount: 12,
+				total_claimed: 62,
+				remaining_unvested: 63,
+				vesting_block: 5,
+			},
+			crate::Event::RewardsPaid {
+				account: 3,
+				amount: 13,
+				total_claimed: 75,
+				remaining_unvested: 50,
+				vesting_block: 6,
+			},
 		];
 		assert_eq!(events(), expected);
 	});
@@ -823,13 +1048,51 @@ fn reward_below_vesting_period_works() {
 		);
 
 		let expected = vec![
-			crate::Event::InitialPaymentMade(1, 249),
-			crate::Event::InitialPaymentMade(2, 249),
-			crate::Event::InitialPaymentMade(3, 1),
-			crate::Event::RewardsPaid(3, 1),
-			crate::Event::RewardsPaid(3, 0),
-			crate::Event::RewardsPaid(3, 1),
-			crate::Event::RewardsPaid(3, 3),
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 249,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 249,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 3,
+				amount: 1,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 3,
+				initialized_reward_amount: 2001,
+			},
+			crate::Event::RewardsPaid {
+				account: 3,
+				amount: 1,
+				total_claimed: 2,
+				remaining_unvested: 4,
+				vesting_block: 4,
+			},
+			crate::Event::RewardsPaid {
+				account: 3,
+				amount: 0,
+				total_claimed: 2,
+				remaining_unvested: 4,
+				vesting_block: 5,
+			},
+			crate::Event::RewardsPaid {
+				account: 3,
+				amount: 1,
+				total_claimed: 3,
+				remaining_unvested: 3,
+				vesting_block: 6,
+			},
+			crate::Event::RewardsPaid {
+				account: 3,
+				amount: 3,
+				total_claimed: 6,
+				remaining_unvested: 0,
+				vesting_block: 10,
+			},
 		];
 		assert_eq!(events(), expected);
 	});
@@ -943,7 +1206,7 @@ fn test_relay_signatures_can_change_reward_addresses() {
 		for pair in pairs.clone() {
 			assert!(reward_info
 				.contributed_relay_addresses
-				.contains(&pair.public().into()))
+				.contains(&(pair.public().into(), Some(500))))
 		}
 
 		// Threshold is set to 50%, so we need at least 3 votes to pass
@@ -965,7 +1228,9 @@ fn test_relay_signatures_can_change_reward_addresses() {
 				RuntimeOrigin::signed(1),
 				2,
 				1,
-				insufficient_proofs.clone()
+				insufficient_proofs.clone(),
+				5,
+				None
 			),
 			Error::<Test>::InsufficientNumberOfValidProofs
 		);
@@ -981,7 +1246,9 @@ fn test_relay_signatures_can_change_reward_addresses() {
 			RuntimeOrigin::signed(1),
 			2,
 			1,
-			sufficient_proofs.clone()
+			sufficient_proofs.clone(),
+			5,
+			None
 		));
 
 		// 1 should no longer be payable
@@ -992,5 +1259,2657 @@ fn test_relay_signatures_can_change_reward_addresses() {
 
 		// The reward info should be identical
 		assert_eq!(reward_info, reward_info_2);
+
+		// The event records which relay accounts voted
+		let mut voters: Vec<[u8; 32]> = sufficient_proofs.iter().map(|(v, _)| *v).collect();
+		voters.sort();
+		assert_eq!(
+			events().last(),
+			Some(&crate::Event::RewardAddressUpdated {
+				previous_account: 1,
+				new_account: 2,
+				mechanism: RewardAddressChangeMechanism::RelayKeyVote { voters },
+			})
+		);
+	});
+}
+
+#[test]
+fn relay_votes_can_be_weighted_by_contribution() {
+	empty().execute_with(|| {
+		let pairs = get_ed25519_pairs(3);
+		// The init relay block gets inserted
+		roll_to(2);
+		let init_block = Crowdloan::init_vesting_block();
+
+		// One whale and two small contributions to the same reward account
+		assert_ok!(Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::root(),
+			vec![
+				(pairs[0].public().into(), Some(1), 1000u32.into()),
+				(pairs[1].public().into(), Some(1), 300u32.into()),
+				(pairs[2].public().into(), Some(1), 200u32.into()),
+				([9u8; 32].into(), None, 1000u32.into()),
+			],
+		));
+		assert_ok!(Crowdloan::complete_initialization(
+			RuntimeOrigin::root(),
+			init_block + VESTING
+		));
+
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+		payload.append(&mut 2u64.encode());
+		payload.append(&mut 1u64.encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+		let proof = |i: usize| -> ([u8; 32], MultiSignature) {
+			(pairs[i].public().into(), pairs[i].sign(&payload).into())
+		};
+
+		// One vote out of three is not enough
+		assert_noop!(
+			Crowdloan::change_association_with_relay_keys(
+				RuntimeOrigin::signed(1),
+				2,
+				1,
+				vec![proof(0)],
+				3,
+				None
+			),
+			Error::<Test>::InsufficientNumberOfValidProofs
+		);
+
+		TestRewardAddressRelayVoteMode::set(RelayVoteMode::ContributionWeighted);
+
+		// Two small contributions only account for 500 out of 1500
+		assert_noop!(
+			Crowdloan::change_association_with_relay_keys(
+				RuntimeOrigin::signed(1),
+				2,
+				1,
+				vec![proof(1), proof(2)],
+				3,
+				None
+			),
+			Error::<Test>::InsufficientNumberOfValidProofs
+		);
+
+		// The whale alone holds 1000 out of 1500
+		assert_ok!(Crowdloan::change_association_with_relay_keys(
+			RuntimeOrigin::signed(1),
+			2,
+			1,
+			vec![proof(0)],
+			3,
+			None
+		));
+		assert!(Crowdloan::accounts_payable(&1).is_none());
+		assert_eq!(Crowdloan::accounts_payable(&2).unwrap().total_reward, 1500);
+		assert_eq!(
+			events().last(),
+			Some(&crate::Event::RewardAddressUpdated {
+				previous_account: 1,
+				new_account: 2,
+				mechanism: RewardAddressChangeMechanism::RelayKeyVote {
+					voters: vec![*pairs[0].public().as_array_ref()]
+				},
+			})
+		);
+	});
+}
+
+#[test]
+fn simulate_initialize_reports_rows_without_changing_storage() {
+	empty().execute_with(|| {
+		// The init relay block gets inserted
+		roll_to(2);
+		assert_ok!(Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::root(),
+			vec![([1u8; 32].into(), Some(1), 500u32.into())]
+		));
+		let events_before = events();
+
+		// 2000 is what is left to initialize in the pot
+		let rewards = vec![
+			([2u8; 32].into(), Some(2), 500u32.into()),
+			([3u8; 32].into(), Some(1), 500u32.into()),
+			([1u8; 32].into(), Some(1), 500u32.into()),
+			([4u8; 32].into(), None, 300u32.into()),
+			([5u8; 32].into(), None, 300u32.into()),
+		];
+		// The whole batch goes beyond the pot, so none of it would be initialized
+		let simulation = Crowdloan::simulate_initialize(rewards.clone()).unwrap();
+		assert_eq!(
+			simulation,
+			InitializationSimulation {
+				outcomes: vec![InitializationOutcome::WouldExceedPot; 5],
+				initialized_reward_amount: 400,
+				total_contributors: 1,
+				pot: 2400,
+			}
+		);
+
+		let simulation = Crowdloan::simulate_initialize(rewards[..4].to_vec()).unwrap();
+		assert_eq!(
+			simulation,
+			InitializationSimulation {
+				outcomes: vec![
+					InitializationOutcome::Inserted,
+					InitializationOutcome::MergedIntoExistingAccount,
+					InitializationOutcome::SkippedAlreadyInitialized,
+					InitializationOutcome::Inserted,
+				],
+				initialized_reward_amount: 1500,
+				total_contributors: 4,
+				pot: 2200,
+			}
+		);
+
+		// Nothing changed
+		assert!(Crowdloan::accounts_payable(&2).is_none());
+		assert!(Crowdloan::unassociated_contributions(&[4u8; 32]).is_none());
+		assert_eq!(Crowdloan::init_reward_amount(), 400);
+		assert_eq!(Crowdloan::total_contributors(), 1);
+		assert_eq!(Crowdloan::pot(), 2400);
+		assert_eq!(events(), events_before);
+
+		// The real thing rejects the whole batch
+		assert_noop!(
+			Crowdloan::initialize_reward_vec(RuntimeOrigin::root(), rewards.clone()),
+			Error::<Test>::BatchBeyondFundPot
+		);
+
+		// And matches the simulation once the offending row is removed
+		assert_ok!(Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::root(),
+			rewards[..4].to_vec()
+		));
+		assert_eq!(Crowdloan::init_reward_amount(), 1500);
+		assert_eq!(Crowdloan::total_contributors(), 4);
+		assert_eq!(Crowdloan::pot(), 2200);
+	});
+}
+
+#[test]
+fn simulate_initialize_fails_like_initialize_reward_vec() {
+	empty().execute_with(|| {
+		// The init relay block gets inserted
+		roll_to(2);
+		let init_block = Crowdloan::init_vesting_block();
+
+		let too_many: Vec<([u8; 32], Option<u64>, u128)> =
+			(1..=9).map(|i| ([i as u8; 32], Some(i), 1)).collect();
+		assert_eq!(
+			Crowdloan::simulate_initialize(too_many),
+			Err(Error::<Test>::TooManyContributors.into())
+		);
+
+		assert_ok!(Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::root(),
+			vec![([1u8; 32].into(), Some(1), 2500u32.into())]
+		));
+		assert_ok!(Crowdloan::complete_initialization(
+			RuntimeOrigin::root(),
+			init_block + VESTING
+		));
+		assert_eq!(
+			Crowdloan::simulate_initialize(vec![([2u8; 32].into(), Some(2), 1u32.into())]),
+			Err(Error::<Test>::RewardVecAlreadyInitialized.into())
+		);
+	});
+}
+
+fn initialize_and_complete() -> Vec<sp_core::ed25519::Pair> {
+	let pairs = get_ed25519_pairs(3);
+	// The init relay block gets inserted
+	roll_to(2);
+	let init_block = Crowdloan::init_vesting_block();
+	assert_ok!(Crowdloan::initialize_reward_vec(
+		RuntimeOrigin::root(),
+		vec![
+			([1u8; 32].into(), Some(1), 500u32.into()),
+			([2u8; 32].into(), Some(2), 500u32.into()),
+			(pairs[0].public().into(), None, 500u32.into()),
+			(pairs[1].public().into(), None, 500u32.into()),
+			(pairs[2].public().into(), None, 500u32.into())
+		]
+	));
+	assert_ok!(Crowdloan::complete_initialization(
+		RuntimeOrigin::root(),
+		init_block + VESTING
+	));
+	pairs
+}
+
+#[test]
+fn adjust_reward_works() {
+	empty().execute_with(|| {
+		// Cannot amend before the initialization is complete
+		assert_noop!(
+			Crowdloan::adjust_reward(
+				RuntimeOrigin::root(),
+				ContributorAccount::Native(1),
+				1000u32.into()
+			),
+			Error::<Test>::RewardVecNotFullyInitializedYet
+		);
+
+		let pairs = initialize_and_complete();
+		assert_eq!(Crowdloan::pot(), 2300);
+
+		roll_to(4);
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(1)));
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 200);
+
+		// Only the admin origin can amend rewards
+		assert_noop!(
+			Crowdloan::adjust_reward(
+				RuntimeOrigin::signed(1),
+				ContributorAccount::Native(1),
+				1000u32.into()
+			),
+			DispatchError::BadOrigin
+		);
+
+		// Increasing tops up the pot from the funding account, without issuing anything
+		let issuance = Balances::total_issuance();
+		assert_ok!(Crowdloan::adjust_reward(
+			RuntimeOrigin::root(),
+			ContributorAccount::Native(1),
+			1000u32.into()
+		));
+		assert_eq!(Crowdloan::pot(), 2700);
+		assert_eq!(Balances::free_balance(99), 9500);
+		assert_eq!(Balances::total_issuance(), issuance);
+		assert_eq!(Crowdloan::init_reward_amount(), 2800);
+		// The only relay account contributed the whole reward
+		assert_eq!(
+			Crowdloan::accounts_payable(&1)
+				.unwrap()
+				.contributed_relay_addresses,
+			vec![([1u8; 32], Some(1000))]
+		);
+
+		// The new reward vests on the same schedule: 200 + 800 * 2 / 8
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(1)));
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 400);
+
+		// Cannot go below what was already claimed
+		assert_noop!(
+			Crowdloan::adjust_reward(
+				RuntimeOrigin::root(),
+				ContributorAccount::Native(1),
+				300u32.into()
+			),
+			Error::<Test>::RewardBelowClaimedAmount
+		);
+
+		// Decreasing returns the difference to the funding account
+		assert_ok!(Crowdloan::adjust_reward(
+			RuntimeOrigin::root(),
+			ContributorAccount::Relay(pairs[0].public().into()),
+			100u32.into()
+		));
+		assert_eq!(Crowdloan::pot(), 2100);
+		assert_eq!(Balances::free_balance(99), 9900);
+		assert_eq!(Crowdloan::init_reward_amount(), 2400);
+		assert_eq!(
+			Crowdloan::unassociated_contributions(pairs[0].public().as_array_ref())
+				.unwrap()
+				.total_reward,
+			100
+		);
+
+		// Going below the vested amount does not pay anything until vesting catches up
+		assert_ok!(Crowdloan::adjust_reward(
+			RuntimeOrigin::root(),
+			ContributorAccount::Native(2),
+			200u32.into()
+		));
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(2)));
+		assert_eq!(Crowdloan::accounts_payable(&2).unwrap().claimed_reward, 100);
+
+		let expected = vec![
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 100,
+				total_claimed: 200,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+			crate::Event::RewardAdjusted {
+				contributor: ContributorAccount::Native(1),
+				previous_reward: 500,
+				new_reward: 1000,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 200,
+				total_claimed: 400,
+				remaining_unvested: 600,
+				vesting_block: 4,
+			},
+			crate::Event::RewardAdjusted {
+				contributor: ContributorAccount::Relay(pairs[0].public().into()),
+				previous_reward: 500,
+				new_reward: 100,
+			},
+			crate::Event::RewardAdjusted {
+				contributor: ContributorAccount::Native(2),
+				previous_reward: 500,
+				new_reward: 200,
+			},
+			crate::Event::RewardsPaid {
+				account: 2,
+				amount: 0,
+				total_claimed: 100,
+				remaining_unvested: 120,
+				vesting_block: 4,
+			},
+		];
+		assert_eq!(events(), expected);
+	});
+}
+
+#[test]
+fn add_late_contributor_works() {
+	empty().execute_with(|| {
+		let pairs = initialize_and_complete();
+
+		roll_to(4);
+		assert_ok!(Crowdloan::add_late_contributor(
+			RuntimeOrigin::root(),
+			[9u8; 32].into(),
+			Some(9),
+			500u32.into()
+		));
+		assert_eq!(Crowdloan::pot(), 2700);
+		assert_eq!(Balances::free_balance(99), 9500);
+		assert_eq!(Crowdloan::total_contributors(), 6);
+		assert_eq!(Crowdloan::init_reward_amount(), 2700);
+		assert!(Crowdloan::claimed_relay_chain_ids(&[9u8; 32]).is_some());
+
+		// Vested rewards can be claimed right away
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(9)));
+		assert_eq!(Crowdloan::accounts_payable(&9).unwrap().claimed_reward, 200);
+
+		// Already known contributions cannot be added again
+		assert_noop!(
+			Crowdloan::add_late_contributor(
+				RuntimeOrigin::root(),
+				[9u8; 32].into(),
+				Some(9),
+				500u32.into()
+			),
+			Error::<Test>::AlreadyAssociated
+		);
+		assert_noop!(
+			Crowdloan::add_late_contributor(
+				RuntimeOrigin::root(),
+				pairs[0].public().into(),
+				None,
+				500u32.into()
+			),
+			Error::<Test>::AlreadyAssociated
+		);
+
+		let expected = vec![
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 9,
+				amount: 100,
+			},
+			crate::Event::LateContributorAdded {
+				relay_account: [9u8; 32],
+				native_account: Some(9),
+				reward: 500,
+			},
+			crate::Event::RewardsPaid {
+				account: 9,
+				amount: 100,
+				total_claimed: 200,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+		];
+		assert_eq!(events(), expected);
+	});
+}
+
+#[test]
+fn remove_contributor_works() {
+	empty().execute_with(|| {
+		let pairs = initialize_and_complete();
+
+		roll_to(4);
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(1)));
+
+		// The number of relay addresses can not be underestimated
+		assert_noop!(
+			Crowdloan::remove_contributor(RuntimeOrigin::root(), ContributorAccount::Native(1), 0),
+			Error::<Test>::TooFewRelayAddresses
+		);
+
+		// The unclaimed 300 go back to the funding account
+		let issuance = Balances::total_issuance();
+		let post_info =
+			Crowdloan::remove_contributor(RuntimeOrigin::root(), ContributorAccount::Native(1), 3)
+				.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::remove_contributor(1))
+		);
+		assert_eq!(Crowdloan::pot(), 1900);
+		assert_eq!(Balances::free_balance(99), 10_300);
+		assert_eq!(Balances::total_issuance(), issuance);
+		assert_eq!(Crowdloan::init_reward_amount(), 2000);
+		assert_eq!(Crowdloan::total_contributors(), 4);
+		assert!(Crowdloan::accounts_payable(&1).is_none());
+		assert!(Crowdloan::claimed_relay_chain_ids(&[1u8; 32]).is_none());
+		assert_noop!(
+			Crowdloan::claim(RuntimeOrigin::signed(1)),
+			Error::<Test>::NoAssociatedClaim
+		);
+
+		assert_ok!(Crowdloan::remove_contributor(
+			RuntimeOrigin::root(),
+			ContributorAccount::Relay(pairs[0].public().into()),
+			1
+		));
+		assert_eq!(Crowdloan::pot(), 1400);
+		assert_eq!(Crowdloan::total_contributors(), 3);
+		assert!(Crowdloan::unassociated_contributions(pairs[0].public().as_array_ref()).is_none());
+
+		assert_noop!(
+			Crowdloan::remove_contributor(
+				RuntimeOrigin::root(),
+				ContributorAccount::Relay(pairs[0].public().into()),
+				1
+			),
+			Error::<Test>::NoAssociatedClaim
+		);
+
+		// The relay account can be added again with the right reward
+		assert_ok!(Crowdloan::add_late_contributor(
+			RuntimeOrigin::root(),
+			[1u8; 32].into(),
+			Some(1),
+			250u32.into()
+		));
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().total_reward, 250);
+
+		let expected = vec![
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 100,
+				total_claimed: 200,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+			crate::Event::ContributorRemoved {
+				contributor: ContributorAccount::Native(1),
+				refunded: 300,
+			},
+			crate::Event::ContributorRemoved {
+				contributor: ContributorAccount::Relay(pairs[0].public().into()),
+				refunded: 500,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 50,
+			},
+			crate::Event::LateContributorAdded {
+				relay_account: [1u8; 32],
+				native_account: Some(1),
+				reward: 250,
+			},
+		];
+		assert_eq!(events(), expected);
+	});
+}
+
+#[test]
+fn remove_contributor_drops_what_was_pending_for_the_reward() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+		TestRewardAddressChangeDelay::set(5);
+
+		// 1 queues a change, gets a recovery request and approves a merge
+		assert_ok!(Crowdloan::update_reward_address(
+			RuntimeOrigin::signed(1),
+			8,
+			1
+		));
+		assert_ok!(Crowdloan::request_recovery(
+			RuntimeOrigin::signed(2),
+			1,
+			9,
+			sp_core::H256::repeat_byte(7)
+		));
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert_ok!(Crowdloan::approve_merge(RuntimeOrigin::signed(1), 2));
+
+		assert_ok!(Crowdloan::remove_contributor(
+			RuntimeOrigin::root(),
+			ContributorAccount::Native(1),
+			1
+		));
+		assert!(Crowdloan::pending_reward_address_changes(&1).is_none());
+		assert!(Crowdloan::recovery_requests(&1).is_none());
+		assert!(Crowdloan::merge_approvals(&1, &2).is_none());
+		// The requester did nothing wrong, so the deposit is returned
+		assert_eq!(Balances::reserved_balance(2), 0);
+
+		let events = events();
+		assert!(
+			events.contains(&crate::Event::RewardAddressChangeCancelled {
+				previous_account: 1,
+				new_account: 8,
+				relay_account: None,
+			})
+		);
+		assert!(events.contains(&crate::Event::RecoveryCancelled {
+			lost_account: 1,
+			slashed: 0,
+		}));
+	});
+}
+
+#[test]
+fn initialization_origin_can_initialize() {
+	empty().execute_with(|| {
+		// The init relay block gets inserted
+		roll_to(2);
+		let init_block = Crowdloan::init_vesting_block();
+
+		// Any other signed account is rejected
+		assert_noop!(
+			Crowdloan::initialize_reward_vec(
+				RuntimeOrigin::signed(1),
+				vec![([1u8; 32].into(), Some(1), 2500u32.into())]
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::signed(Initializer::get()),
+			vec![([1u8; 32].into(), Some(1), 2500u32.into())]
+		));
+
+		assert_noop!(
+			Crowdloan::complete_initialization(RuntimeOrigin::signed(1), init_block + VESTING),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(Crowdloan::complete_initialization(
+			RuntimeOrigin::signed(Initializer::get()),
+			init_block + VESTING
+		));
+		assert!(Crowdloan::initialized());
+	});
+}
+
+#[test]
+fn paused_calls_are_rejected() {
+	let pairs = get_ed25519_pairs(3);
+	let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+	payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+	payload.append(&mut 3u64.encode());
+	payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+	let signature: MultiSignature = pairs[0].sign(&payload).into();
+	empty().execute_with(|| {
+		initialize_and_complete();
+		roll_to(4);
+
+		// Only the pause origin can pause
+		assert_noop!(
+			Crowdloan::pause(RuntimeOrigin::signed(1), PausableCall::Claim),
+			DispatchError::BadOrigin
+		);
+
+		for call in [
+			PausableCall::Claim,
+			PausableCall::AssociateNativeIdentity,
+			PausableCall::ChangeAssociationWithRelayKeys,
+			PausableCall::UpdateRewardAddress,
+		] {
+			assert_ok!(Crowdloan::pause(RuntimeOrigin::root(), call));
+		}
+		assert_noop!(
+			Crowdloan::pause(RuntimeOrigin::root(), PausableCall::Claim),
+			Error::<Test>::CallAlreadyPaused
+		);
+
+		assert_noop!(
+			Crowdloan::claim(RuntimeOrigin::signed(1)),
+			Error::<Test>::CallPaused
+		);
+		assert_noop!(
+			Crowdloan::associate_native_identity(
+				RuntimeOrigin::signed(4),
+				3,
+				pairs[0].public().into(),
+				signature.clone(),
+				None
+			),
+			Error::<Test>::CallPaused
+		);
+		assert_noop!(
+			Crowdloan::change_association_with_relay_keys(
+				RuntimeOrigin::signed(1),
+				3,
+				1,
+				vec![],
+				1,
+				None
+			),
+			Error::<Test>::CallPaused
+		);
+		assert_noop!(
+			Crowdloan::update_reward_address(RuntimeOrigin::signed(1), 3, 1),
+			Error::<Test>::CallPaused
+		);
+
+		// Calls are unpaused independently
+		assert_ok!(Crowdloan::unpause(
+			RuntimeOrigin::root(),
+			PausableCall::UpdateRewardAddress
+		));
+		assert_noop!(
+			Crowdloan::unpause(RuntimeOrigin::root(), PausableCall::UpdateRewardAddress),
+			Error::<Test>::CallNotPaused
+		);
+		assert_ok!(Crowdloan::update_reward_address(
+			RuntimeOrigin::signed(1),
+			3,
+			1
+		));
+		assert_noop!(
+			Crowdloan::claim(RuntimeOrigin::signed(3)),
+			Error::<Test>::CallPaused
+		);
+	});
+}
+
+#[test]
+fn vesting_keeps_accruing_while_paused() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+
+		roll_to(4);
+		assert_ok!(Crowdloan::pause(RuntimeOrigin::root(), PausableCall::Claim));
+
+		roll_to(8);
+		assert_ok!(Crowdloan::unpause(
+			RuntimeOrigin::root(),
+			PausableCall::Claim
+		));
+
+		// Same as if claims had never been paused
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(1)));
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 400);
+
+		let expected = vec![
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::CallPaused {
+				call: PausableCall::Claim,
+			},
+			crate::Event::CallUnpaused {
+				call: PausableCall::Claim,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 300,
+				total_claimed: 400,
+				remaining_unvested: 100,
+				vesting_block: 8,
+			},
+		];
+		assert_eq!(events(), expected);
+	});
+}
+
+#[test]
+fn claim_for_pays_the_beneficiary() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+
+		roll_to(4);
+		let caller_balance = Balances::free_balance(7);
+		assert_ok!(Crowdloan::claim_for(RuntimeOrigin::signed(7), 1));
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 200);
+		assert_eq!(Balances::free_balance(1), 200);
+		assert_eq!(Balances::free_balance(7), caller_balance);
+
+		// Same checks as claim
+		assert_noop!(
+			Crowdloan::claim_for(RuntimeOrigin::signed(7), 7),
+			Error::<Test>::NoAssociatedClaim
+		);
+		assert_ok!(Crowdloan::pause(RuntimeOrigin::root(), PausableCall::Claim));
+		assert_noop!(
+			Crowdloan::claim_for(RuntimeOrigin::signed(7), 1),
+			Error::<Test>::CallPaused
+		);
+
+		let expected = vec![
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 100,
+				total_claimed: 200,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+			crate::Event::CallPaused {
+				call: PausableCall::Claim,
+			},
+		];
+		assert_eq!(events(), expected);
+	});
+}
+
+#[test]
+fn claim_many_skips_accounts_with_nothing_to_claim() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+
+		roll_to(4);
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(1)));
+
+		// 1 already claimed in this block and 7 has no reward, only 2 gets paid
+		assert_ok!(Crowdloan::claim_many(
+			RuntimeOrigin::signed(7),
+			vec![1, 2, 7]
+		));
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 200);
+		assert_eq!(Crowdloan::accounts_payable(&2).unwrap().claimed_reward, 200);
+		assert_eq!(Balances::free_balance(2), 200);
+
+		// The batch is bounded by MaxClaimBatch
+		assert_noop!(
+			Crowdloan::claim_many(RuntimeOrigin::signed(7), vec![1, 2, 3, 4, 5]),
+			Error::<Test>::TooManyClaims
+		);
+
+		roll_to(8);
+		assert_ok!(Crowdloan::claim_many(RuntimeOrigin::signed(7), vec![1, 2]));
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 400);
+		assert_eq!(Crowdloan::accounts_payable(&2).unwrap().claimed_reward, 400);
+
+		let expected = vec![
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 100,
+				total_claimed: 200,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+			crate::Event::RewardsPaid {
+				account: 2,
+				amount: 100,
+				total_claimed: 200,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+			crate::Event::RewardsBatchPaid {
+				accounts_paid: 1,
+				total_paid: 100,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 200,
+				total_claimed: 400,
+				remaining_unvested: 100,
+				vesting_block: 8,
+			},
+			crate::Event::RewardsPaid {
+				account: 2,
+				amount: 200,
+				total_claimed: 400,
+				remaining_unvested: 100,
+				vesting_block: 8,
+			},
+			crate::Event::RewardsBatchPaid {
+				accounts_paid: 2,
+				total_paid: 400,
+			},
+		];
+		assert_eq!(events(), expected);
+	});
+}
+
+#[test]
+fn on_idle_pays_vested_rewards_when_enabled() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+		roll_to(4);
+
+		// Auto payouts are disabled by default
+		assert_eq!(Crowdloan::on_idle(4, Weight::MAX), Weight::zero());
+		assert!(Crowdloan::auto_payout_cursor().is_none());
+
+		TestMaxAutoPayoutsPerBlock::set(1);
+
+		// Nothing gets paid below the minimum, but the cursor moves forward
+		TestMinimumAutoPayout::set(150);
+		assert!(Crowdloan::on_idle(4, Weight::MAX) != Weight::zero());
+		assert!(Crowdloan::auto_payout_cursor().is_some());
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 100);
+		assert_eq!(Crowdloan::accounts_payable(&2).unwrap().claimed_reward, 100);
+		TestMinimumAutoPayout::set(0);
+
+		// Pays the second account, then reaches the end and starts over with the first one
+		Crowdloan::on_idle(4, Weight::MAX);
+		Crowdloan::on_idle(4, Weight::MAX);
+		assert!(Crowdloan::auto_payout_cursor().is_none());
+		Crowdloan::on_idle(4, Weight::MAX);
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 200);
+		assert_eq!(Crowdloan::accounts_payable(&2).unwrap().claimed_reward, 200);
+		assert_eq!(Balances::free_balance(1), 200);
+		assert_eq!(Balances::free_balance(2), 200);
+
+		roll_to(8);
+		// Nothing happens without enough leftover weight
+		assert_eq!(Crowdloan::on_idle(8, Weight::zero()), Weight::zero());
+		// Nor while claims are paused
+		assert_ok!(Crowdloan::pause(RuntimeOrigin::root(), PausableCall::Claim));
+		Crowdloan::on_idle(8, Weight::MAX);
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 200);
+		assert_eq!(Crowdloan::accounts_payable(&2).unwrap().claimed_reward, 200);
+	});
+}
+
+#[test]
+fn claim_to_pays_another_account() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+
+		roll_to(4);
+		assert_ok!(Crowdloan::claim_to(RuntimeOrigin::signed(1), 7));
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 200);
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(Balances::free_balance(7), 100);
+
+		// The reward stays with 1
+		assert!(Crowdloan::accounts_payable(&7).is_none());
+		roll_to(8);
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::free_balance(1), 300);
+
+		// Only the owner can direct its payout
+		assert_noop!(
+			Crowdloan::claim_to(RuntimeOrigin::signed(7), 7),
+			Error::<Test>::NoAssociatedClaim
+		);
+
+		let expected = vec![
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::RewardsPaidTo {
+				account: 1,
+				dest: 7,
+				amount: 100,
+				total_claimed: 200,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 200,
+				total_claimed: 400,
+				remaining_unvested: 100,
+				vesting_block: 8,
+			},
+		];
+		assert_eq!(events(), expected);
+	});
+}
+
+#[test]
+fn claim_amount_pays_part_of_the_vested_reward() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+
+		roll_to(4);
+		// 100 are vested at this point
+		assert_noop!(
+			Crowdloan::claim_amount(RuntimeOrigin::signed(1), 101),
+			Error::<Test>::ClaimAmountExceedsVestedReward
+		);
+		assert_ok!(Crowdloan::claim_amount(RuntimeOrigin::signed(1), 40));
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 140);
+		assert_eq!(Balances::free_balance(1), 140);
+
+		// The rest can be claimed later
+		assert_noop!(
+			Crowdloan::claim_amount(RuntimeOrigin::signed(1), 61),
+			Error::<Test>::ClaimAmountExceedsVestedReward
+		);
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(1)));
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 200);
+
+		let expected = vec![
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 40,
+				total_claimed: 140,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 60,
+				total_claimed: 200,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+		];
+		assert_eq!(events(), expected);
+	});
+}
+
+#[test]
+fn claim_and_stake_forwards_the_claimed_rewards() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+
+		roll_to(4);
+		assert_ok!(Crowdloan::claim_and_stake(RuntimeOrigin::signed(1), 1));
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 200);
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_eq!(ForwardedRewards::get(), vec![(1, 100, 1)]);
+
+		// A failing hook reverts the whole claim
+		let call: RuntimeCall = crate::Call::claim_and_stake { target: 0 }.into();
+		assert_noop!(
+			call.dispatch(RuntimeOrigin::signed(2)),
+			DispatchError::Other("Target rejects the rewards")
+		);
+		assert_eq!(Crowdloan::accounts_payable(&2).unwrap().claimed_reward, 100);
+		assert_eq!(Balances::free_balance(2), 100);
+
+		let expected = vec![
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::RewardsPaid {
+				account: 1,
+				amount: 100,
+				total_claimed: 200,
+				remaining_unvested: 300,
+				vesting_block: 4,
+			},
+			crate::Event::RewardsForwarded {
+				account: 1,
+				amount: 100,
+				target: 1,
+			},
+		];
+		assert_eq!(events(), expected);
+	});
+}
+
+#[test]
+fn unvested_rewards_can_be_staked_and_unbonded_as_they_vest() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+
+		roll_to(4);
+		// 1 has claimed the initial 100 out of 500, 200 are vested by now
+		assert_noop!(
+			Crowdloan::stake_unvested_rewards(RuntimeOrigin::signed(1), 401),
+			Error::<Test>::StakeAmountExceedsUnclaimedReward
+		);
+		assert_ok!(Crowdloan::stake_unvested_rewards(
+			RuntimeOrigin::signed(1),
+			300
+		));
+		assert_eq!(Crowdloan::accounts_payable(&1).unwrap().claimed_reward, 400);
+		assert_eq!(Crowdloan::staked_unvested_rewards(&1), 300);
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(Balances::reserved_balance(1), 300);
+
+		// Only the 100 vested since the initial payment can be unbonded
+		assert_noop!(
+			Crowdloan::unbond_staked_rewards(RuntimeOrigin::signed(1), 101),
+			Error::<Test>::UnbondAmountExceedsVestedStake
+		);
+		assert_ok!(Crowdloan::unbond_staked_rewards(
+			RuntimeOrigin::signed(1),
+			100
+		));
+		assert_eq!(Crowdloan::staked_unvested_rewards(&1), 200);
+		assert_eq!(Balances::free_balance(1), 200);
+
+		// The reward address is frozen while rewards are staked
+		assert_noop!(
+			Crowdloan::update_reward_address(RuntimeOrigin::signed(1), 8, 1),
+			Error::<Test>::RewardsStaked
+		);
+		// And the contributor can not be removed, which would leave the stake behind
+		assert_noop!(
+			Crowdloan::remove_contributor(RuntimeOrigin::root(), ContributorAccount::Native(1), 1),
+			Error::<Test>::RewardsStaked
+		);
+
+		roll_to(8);
+		assert_ok!(Crowdloan::unbond_staked_rewards(
+			RuntimeOrigin::signed(1),
+			200
+		));
+		assert_eq!(Crowdloan::staked_unvested_rewards(&1), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_ok!(Crowdloan::update_reward_address(
+			RuntimeOrigin::signed(1),
+			8,
+			1
+		));
+
+		let expected = vec![
+			crate::Event::InitialPaymentMade {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::InitialPaymentMade {
+				account: 2,
+				amount: 100,
+			},
+			crate::Event::InitializationCompleted {
+				end_vesting_block: 10,
+				total_contributors: 5,
+				initialized_reward_amount: 2300,
+			},
+			crate::Event::UnvestedRewardsStaked {
+				account: 1,
+				amount: 300,
+			},
+			crate::Event::StakedRewardsUnbonded {
+				account: 1,
+				amount: 100,
+			},
+			crate::Event::StakedRewardsUnbonded {
+				account: 1,
+				amount: 200,
+			},
+			crate::Event::RewardAddressUpdated {
+				previous_account: 1,
+				new_account: 8,
+				mechanism: RewardAddressChangeMechanism::SelfUpdate,
+			},
+		];
+		assert_eq!(events(), expected);
+	});
+}
+
+#[test]
+fn successful_claims_are_free_within_the_budget() {
+	empty().execute_with(|| {
+		let pairs = initialize_and_complete();
+
+		// Associating pays the initial reward, so it is free
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+		payload.append(&mut 3u64.encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+		let signature: MultiSignature = pairs[0].sign(&payload).into();
+		let post_info = Crowdloan::associate_native_identity(
+			RuntimeOrigin::signed(4),
+			3,
+			pairs[0].public().into(),
+			signature,
+			None,
+		)
+		.unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		assert_eq!(Crowdloan::free_claims_used(&3), (0, 1));
+
+		// Two free claims per period
+		roll_to(4);
+		let post_info = Crowdloan::claim(RuntimeOrigin::signed(1)).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		roll_to(5);
+		let post_info = Crowdloan::claim(RuntimeOrigin::signed(1)).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		roll_to(6);
+		let post_info = Crowdloan::claim(RuntimeOrigin::signed(1)).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::Yes);
+		assert_eq!(Crowdloan::free_claims_used(&1), (0, 2));
+
+		// Claims paying nothing are charged and do not use the budget
+		let post_info = Crowdloan::claim(RuntimeOrigin::signed(2)).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		let post_info = Crowdloan::claim(RuntimeOrigin::signed(2)).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::Yes);
+		assert_eq!(Crowdloan::free_claims_used(&2), (0, 1));
+
+		// The budget is restored in the next period
+		roll_to(12);
+		let post_info = Crowdloan::claim(RuntimeOrigin::signed(1)).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		assert_eq!(Crowdloan::free_claims_used(&1), (1, 1));
+	});
+}
+
+#[test]
+fn actual_weight_is_reported() {
+	empty().execute_with(|| {
+		let pairs = get_ed25519_pairs(2);
+		roll_to(2);
+		let init_block = Crowdloan::init_vesting_block();
+
+		// The repeated relay account is skipped
+		let post_info = Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::root(),
+			vec![
+				(pairs[0].public().into(), Some(1), 500u32.into()),
+				(pairs[1].public().into(), Some(1), 500u32.into()),
+				(pairs[0].public().into(), Some(2), 500u32.into()),
+				([3u8; 32].into(), Some(3), 500u32.into()),
+				([4u8; 32].into(), Some(4), 500u32.into()),
+				([5u8; 32].into(), Some(5), 500u32.into()),
+			],
+		)
+		.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(
+				<() as WeightInfo>::initialize_reward_vec(5)
+					.saturating_add(<Test as frame_system::Config>::DbWeight::get().reads(2))
+			)
+		);
+		assert_ok!(Crowdloan::complete_initialization(
+			RuntimeOrigin::root(),
+			init_block + VESTING
+		));
+
+		// Repeated proofs are only verified once
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+		payload.append(&mut 2u64.encode());
+		payload.append(&mut 1u64.encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+		let proof: ([u8; 32], MultiSignature) =
+			(pairs[0].public().into(), pairs[0].sign(&payload).into());
+		let post_info = Crowdloan::change_association_with_relay_keys(
+			RuntimeOrigin::signed(1),
+			2,
+			1,
+			vec![proof.clone(), proof.clone(), proof],
+			2,
+			None,
+		)
+		.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::change_association_with_relay_keys(1, 2))
+		);
+
+		// Accounts with nothing to claim are only read
+		roll_to(4);
+		let post_info = Crowdloan::claim_many(RuntimeOrigin::signed(7), vec![2, 7, 8]).unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(
+				<() as WeightInfo>::claim_many(1)
+					.saturating_add(<Test as frame_system::Config>::DbWeight::get().reads(2))
+			)
+		);
+	});
+}
+
+#[test]
+fn update_reward_address_merges_into_an_approving_account() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+
+		// 1 and 2 have different claim progress
+		roll_to(4);
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(1)));
+
+		// 2 has not consented yet
+		assert_noop!(
+			Crowdloan::update_reward_address(RuntimeOrigin::signed(1), 2, 2),
+			Error::<Test>::AlreadyAssociated
+		);
+		assert_noop!(
+			Crowdloan::approve_merge(RuntimeOrigin::signed(2), 2),
+			Error::<Test>::CannotMergeIntoItself
+		);
+		assert_noop!(
+			Crowdloan::approve_merge(RuntimeOrigin::signed(7), 1),
+			Error::<Test>::NoAssociatedClaim
+		);
+
+		// Approvals can be withdrawn
+		assert_ok!(Crowdloan::approve_merge(RuntimeOrigin::signed(2), 1));
+		assert_ok!(Crowdloan::cancel_merge_approval(
+			RuntimeOrigin::signed(2),
+			1
+		));
+		assert_noop!(
+			Crowdloan::cancel_merge_approval(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::MergeNotApproved
+		);
+		assert_noop!(
+			Crowdloan::update_reward_address(RuntimeOrigin::signed(1), 2, 2),
+			Error::<Test>::AlreadyAssociated
+		);
+
+		assert_ok!(Crowdloan::approve_merge(RuntimeOrigin::signed(2), 1));
+		// The relay addresses of both rewards are counted
+		assert_noop!(
+			Crowdloan::update_reward_address(RuntimeOrigin::signed(1), 2, 1),
+			Error::<Test>::TooFewRelayAddresses
+		);
+		let post_info = Crowdloan::update_reward_address(RuntimeOrigin::signed(1), 2, 3).unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::update_reward_address(2))
+		);
+		assert!(Crowdloan::accounts_payable(&1).is_none());
+		assert!(Crowdloan::merge_approvals(&2, &1).is_none());
+		let info = Crowdloan::accounts_payable(&2).unwrap();
+		assert_eq!(info.total_reward, 1000);
+		assert_eq!(info.claimed_reward, 300);
+		assert_eq!(
+			info.contributed_relay_addresses,
+			vec![([2u8; 32], Some(500)), ([1u8; 32], Some(500))]
+		);
+
+		// 400 of the 1000 are vested
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(2)));
+		assert_eq!(Crowdloan::accounts_payable(&2).unwrap().claimed_reward, 400);
+
+		let events = events();
+		assert!(events.contains(&crate::Event::RewardsMerged {
+			source: 1,
+			target: 2,
+			total_reward: 1000,
+			claimed_reward: 300,
+		}));
+	});
+}
+
+#[test]
+fn relay_keys_can_merge_into_an_approving_account() {
+	empty().execute_with(|| {
+		let pairs = get_ed25519_pairs(2);
+		roll_to(2);
+		let init_block = Crowdloan::init_vesting_block();
+		assert_ok!(Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::root(),
+			vec![
+				(pairs[0].public().into(), Some(1), 500u32.into()),
+				(pairs[1].public().into(), Some(2), 500u32.into()),
+				([3u8; 32].into(), Some(3), 500u32.into()),
+				([4u8; 32].into(), Some(4), 500u32.into()),
+				([5u8; 32].into(), Some(5), 500u32.into()),
+			]
+		));
+		assert_ok!(Crowdloan::complete_initialization(
+			RuntimeOrigin::root(),
+			init_block + VESTING
+		));
+
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+		payload.append(&mut 2u64.encode());
+		payload.append(&mut 1u64.encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+		let proofs: Vec<([u8; 32], MultiSignature)> =
+			vec![(pairs[0].public().into(), pairs[0].sign(&payload).into())];
+
+		assert_noop!(
+			Crowdloan::change_association_with_relay_keys(
+				RuntimeOrigin::signed(1),
+				2,
+				1,
+				proofs.clone(),
+				2,
+				None
+			),
+			Error::<Test>::AlreadyAssociated
+		);
+
+		assert_ok!(Crowdloan::approve_merge(RuntimeOrigin::signed(2), 1));
+		assert_ok!(Crowdloan::change_association_with_relay_keys(
+			RuntimeOrigin::signed(1),
+			2,
+			1,
+			proofs,
+			2,
+			None
+		));
+		let info = Crowdloan::accounts_payable(&2).unwrap();
+		assert_eq!(info.total_reward, 1000);
+		assert_eq!(info.claimed_reward, 200);
+		assert_eq!(
+			info.contributed_relay_addresses,
+			vec![
+				(*pairs[1].public().as_array_ref(), Some(500)),
+				(*pairs[0].public().as_array_ref(), Some(500))
+			]
+		);
+	});
+}
+
+#[test]
+fn split_reward_keeps_the_vesting_position_of_each_slice() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+
+		roll_to(4);
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(1)));
+
+		assert_noop!(
+			Crowdloan::split_reward(
+				RuntimeOrigin::signed(1),
+				vec![
+					(1, Perbill::from_percent(25)),
+					(9, Perbill::from_percent(25)),
+					(10, Perbill::from_percent(25)),
+					(11, Perbill::from_percent(25)),
+				],
+				1
+			),
+			Error::<Test>::TooManySplits
+		);
+		assert_noop!(
+			Crowdloan::split_reward(
+				RuntimeOrigin::signed(1),
+				vec![(9, Perbill::from_percent(100))],
+				0
+			),
+			Error::<Test>::TooFewRelayAddresses
+		);
+		assert_noop!(
+			Crowdloan::split_reward(
+				RuntimeOrigin::signed(1),
+				vec![
+					(9, Perbill::from_percent(50)),
+					(10, Perbill::from_percent(49))
+				],
+				1
+			),
+			Error::<Test>::InvalidSplit
+		);
+		assert_noop!(
+			Crowdloan::split_reward(
+				RuntimeOrigin::signed(1),
+				vec![
+					(9, Perbill::from_percent(50)),
+					(9, Perbill::from_percent(50))
+				],
+				1
+			),
+			Error::<Test>::InvalidSplit
+		);
+		// Every slice needs to get part of the reward
+		assert_noop!(
+			Crowdloan::split_reward(
+				RuntimeOrigin::signed(1),
+				vec![
+					(9, Perbill::from_percent(100)),
+					(10, Perbill::from_percent(0))
+				],
+				1
+			),
+			Error::<Test>::InvalidSplit
+		);
+		assert_noop!(
+			Crowdloan::split_reward(
+				RuntimeOrigin::signed(1),
+				vec![
+					(9, Perbill::from_parts(1)),
+					(10, Perbill::from_parts(999_999_999))
+				],
+				1
+			),
+			Error::<Test>::InvalidSplit
+		);
+		assert_noop!(
+			Crowdloan::split_reward(
+				RuntimeOrigin::signed(1),
+				vec![
+					(2, Perbill::from_percent(50)),
+					(9, Perbill::from_percent(50))
+				],
+				1
+			),
+			Error::<Test>::AlreadyAssociated
+		);
+
+		// Splitting moves the reward, so it is paused along with update_reward_address
+		assert_ok!(Crowdloan::pause(
+			RuntimeOrigin::root(),
+			PausableCall::UpdateRewardAddress
+		));
+		assert_noop!(
+			Crowdloan::split_reward(
+				RuntimeOrigin::signed(1),
+				vec![(9, Perbill::from_percent(100))],
+				1
+			),
+			Error::<Test>::CallPaused
+		);
+		assert_ok!(Crowdloan::unpause(
+			RuntimeOrigin::root(),
+			PausableCall::UpdateRewardAddress
+		));
+
+		// 200 claimed and 300 unclaimed, the leftovers go to 10
+		assert_ok!(Crowdloan::split_reward(
+			RuntimeOrigin::signed(1),
+			vec![
+				(1, Perbill::from_percent(33)),
+				(9, Perbill::from_percent(33)),
+				(10, Perbill::from_percent(34)),
+			],
+			1
+		));
+		// The contribution of the relay account is divided in the same way
+		for (account, total_reward, claimed_reward) in [(1, 165, 66), (9, 165, 66), (10, 170, 68)] {
+			let info = Crowdloan::accounts_payable(&account).unwrap();
+			assert_eq!(info.total_reward, total_reward);
+			assert_eq!(info.claimed_reward, claimed_reward);
+			assert_eq!(
+				info.contributed_relay_addresses,
+				vec![([1u8; 32], Some(total_reward))]
+			);
+		}
+		assert_eq!(Crowdloan::relay_account_slices(&[1u8; 32]), Some(2));
+
+		// Each slice keeps vesting as the original reward, 80% is vested by now
+		roll_to(8);
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(9)));
+		assert_eq!(Crowdloan::accounts_payable(&9).unwrap().claimed_reward, 132);
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(10)));
+		assert_eq!(
+			Crowdloan::accounts_payable(&10).unwrap().claimed_reward,
+			136
+		);
+
+		// Removing a slice does not release the relay account held by the other slices
+		assert_ok!(Crowdloan::remove_contributor(
+			RuntimeOrigin::root(),
+			ContributorAccount::Native(9),
+			1
+		));
+		assert_eq!(Crowdloan::total_contributors(), 5);
+		assert!(Crowdloan::claimed_relay_chain_ids(&[1u8; 32]).is_some());
+		assert_noop!(
+			Crowdloan::add_late_contributor(
+				RuntimeOrigin::root(),
+				[1u8; 32].into(),
+				Some(12),
+				500u32.into()
+			),
+			Error::<Test>::AlreadyAssociated
+		);
+
+		// Merging slices back adds their parts of the contribution together
+		assert_ok!(Crowdloan::approve_merge(RuntimeOrigin::signed(1), 10));
+		assert_ok!(Crowdloan::update_reward_address(
+			RuntimeOrigin::signed(10),
+			1,
+			2
+		));
+		assert_eq!(
+			Crowdloan::accounts_payable(&1)
+				.unwrap()
+				.contributed_relay_addresses,
+			vec![([1u8; 32], Some(335))]
+		);
+		assert!(Crowdloan::relay_account_slices(&[1u8; 32]).is_none());
+
+		// The last entry holding the relay account releases it
+		assert_ok!(Crowdloan::remove_contributor(
+			RuntimeOrigin::root(),
+			ContributorAccount::Native(1),
+			1
+		));
+		assert_eq!(Crowdloan::total_contributors(), 4);
+		assert!(Crowdloan::claimed_relay_chain_ids(&[1u8; 32]).is_none());
+	});
+}
+
+#[test]
+fn transfer_position_keeps_the_vesting_schedule() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+
+		roll_to(4);
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(1)));
+
+		assert_noop!(
+			Crowdloan::transfer_position(RuntimeOrigin::signed(1), 9, Perbill::from_percent(50)),
+			Error::<Test>::TransfersDisabled
+		);
+		assert_noop!(
+			Crowdloan::set_transfers_enabled(RuntimeOrigin::signed(1), true),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Crowdloan::set_transfers_enabled(
+			RuntimeOrigin::root(),
+			true
+		));
+		assert_noop!(
+			Crowdloan::transfer_position(RuntimeOrigin::signed(1), 1, Perbill::from_percent(50)),
+			Error::<Test>::InvalidTransfer
+		);
+		assert_noop!(
+			Crowdloan::transfer_position(RuntimeOrigin::signed(1), 2, Perbill::from_percent(50)),
+			Error::<Test>::AlreadyAssociated
+		);
+
+		// Transferring moves the reward, so it is paused along with update_reward_address
+		assert_ok!(Crowdloan::pause(
+			RuntimeOrigin::root(),
+			PausableCall::UpdateRewardAddress
+		));
+		assert_noop!(
+			Crowdloan::transfer_position(RuntimeOrigin::signed(1), 9, Perbill::from_percent(50)),
+			Error::<Test>::CallPaused
+		);
+		assert_ok!(Crowdloan::unpause(
+			RuntimeOrigin::root(),
+			PausableCall::UpdateRewardAddress
+		));
+
+		// 500 total and 200 claimed, half of each is transferred
+		assert_ok!(Crowdloan::transfer_position(
+			RuntimeOrigin::signed(1),
+			9,
+			Perbill::from_percent(50)
+		));
+		for account in [1, 9] {
+			let info = Crowdloan::accounts_payable(&account).unwrap();
+			assert_eq!(info.total_reward, 250);
+			assert_eq!(info.claimed_reward, 100);
+		}
+		assert_eq!(
+			Crowdloan::accounts_payable(&1)
+				.unwrap()
+				.contributed_relay_addresses,
+			vec![([1u8; 32], Some(250))]
+		);
+		// Relay keys of 1 have no say over the transferred reward
+		assert!(Crowdloan::accounts_payable(&9)
+			.unwrap()
+			.contributed_relay_addresses
+			.is_empty());
+		assert_noop!(
+			Crowdloan::change_association_with_relay_keys(
+				RuntimeOrigin::signed(9),
+				10,
+				9,
+				vec![],
+				0,
+				None
+			),
+			Error::<Test>::InsufficientNumberOfValidProofs
+		);
+
+		// Nothing more is vested yet for the receiver
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(9)));
+		assert_eq!(Crowdloan::accounts_payable(&9).unwrap().claimed_reward, 100);
+		assert_eq!(Balances::free_balance(&9), 0);
+
+		// The rest goes into 2 once it approves the merge
+		assert_ok!(Crowdloan::approve_merge(RuntimeOrigin::signed(2), 1));
+		assert_ok!(Crowdloan::transfer_position(
+			RuntimeOrigin::signed(1),
+			2,
+			Perbill::one()
+		));
+		assert!(Crowdloan::accounts_payable(&1).is_none());
+		assert!(Crowdloan::merge_approvals(&2, &1).is_none());
+		let info = Crowdloan::accounts_payable(&2).unwrap();
+		assert_eq!(info.total_reward, 750);
+		assert_eq!(info.claimed_reward, 200);
+
+		// Vesting goes on at the same pace, 60% is vested by now
+		roll_to(6);
+		assert_ok!(Crowdloan::claim(RuntimeOrigin::signed(9)));
+		assert_eq!(Crowdloan::accounts_payable(&9).unwrap().claimed_reward, 150);
+
+		let expected = vec![
+			crate::Event::TransfersEnabledSet { enabled: true },
+			crate::Event::PositionTransferred {
+				source: 1,
+				dest: 9,
+				total_reward: 250,
+				claimed_reward: 100,
+			},
+		];
+		assert!(events()
+			.windows(2)
+			.any(|window| window == expected.as_slice()));
+	});
+}
+
+#[test]
+fn migration_to_v1_back_fills_the_reward_of_each_relay_account() {
+	empty().execute_with(|| {
+		StorageVersion::new(0).put::<Crowdloan>();
+		frame_support::storage::unhashed::put(
+			&AccountsPayable::<Test>::hashed_key_for(&1),
+			&OldRewardInfo {
+				total_reward: 500u128,
+				claimed_reward: 100u128,
+				contributed_relay_addresses: vec![[1u8; 32]],
+			},
+		);
+		// How this reward was split among the relay accounts was never stored, so it is unknown
+		frame_support::storage::unhashed::put(
+			&AccountsPayable::<Test>::hashed_key_for(&2),
+			&OldRewardInfo {
+				total_reward: 1000u128,
+				claimed_reward: 200u128,
+				contributed_relay_addresses: vec![[2u8; 32], [3u8; 32], [4u8; 32]],
+			},
+		);
+		frame_support::storage::unhashed::put(
+			&UnassociatedContributions::<Test>::hashed_key_for(&[5u8; 32]),
+			&OldRewardInfo {
+				total_reward: 300u128,
+				claimed_reward: 0u128,
+				contributed_relay_addresses: vec![[5u8; 32]],
+			},
+		);
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Crowdloan::on_chain_storage_version(), 1);
+		let info = Crowdloan::accounts_payable(&1).unwrap();
+		assert_eq!(info.total_reward, 500);
+		assert_eq!(info.claimed_reward, 100);
+		assert_eq!(
+			info.contributed_relay_addresses,
+			vec![([1u8; 32], Some(500))]
+		);
+		assert_eq!(
+			Crowdloan::accounts_payable(&2)
+				.unwrap()
+				.contributed_relay_addresses,
+			vec![([2u8; 32], None), ([3u8; 32], None), ([4u8; 32], None)]
+		);
+		assert_eq!(
+			Crowdloan::unassociated_contributions(&[5u8; 32])
+				.unwrap()
+				.contributed_relay_addresses,
+			vec![([5u8; 32], Some(300))]
+		);
+
+		// Running it again does not touch the migrated entries
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(
+			Crowdloan::accounts_payable(&1)
+				.unwrap()
+				.contributed_relay_addresses,
+			vec![([1u8; 32], Some(500))]
+		);
+	});
+}
+
+#[test]
+fn weighted_relay_votes_count_unknown_contributions_per_account() {
+	empty().execute_with(|| {
+		let pairs = get_ed25519_pairs(3);
+		// The init relay block gets inserted
+		roll_to(2);
+		let init_block = Crowdloan::init_vesting_block();
+		assert_ok!(Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::root(),
+			vec![
+				(pairs[0].public().into(), Some(1), 1000u32.into()),
+				(pairs[1].public().into(), Some(1), 300u32.into()),
+				(pairs[2].public().into(), Some(1), 200u32.into()),
+				([9u8; 32].into(), None, 1000u32.into()),
+			],
+		));
+		assert_ok!(Crowdloan::complete_initialization(
+			RuntimeOrigin::root(),
+			init_block + VESTING
+		));
+		// As MigrateToV1 leaves an entry that merged several contributions
+		AccountsPayable::<Test>::mutate(&1, |info| {
+			for (_, reward) in &mut info.as_mut().unwrap().contributed_relay_addresses {
+				*reward = None;
+			}
+		});
+		TestRewardAddressRelayVoteMode::set(RelayVoteMode::ContributionWeighted);
+
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+		payload.append(&mut 2u64.encode());
+		payload.append(&mut 1u64.encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+		let proof = |i: usize| -> ([u8; 32], MultiSignature) {
+			(pairs[i].public().into(), pairs[i].sign(&payload).into())
+		};
+
+		// The whale is no longer known to be one, so it only has one vote out of three
+		assert_noop!(
+			Crowdloan::change_association_with_relay_keys(
+				RuntimeOrigin::signed(1),
+				2,
+				1,
+				vec![proof(0)],
+				3,
+				None
+			),
+			Error::<Test>::InsufficientNumberOfValidProofs
+		);
+		assert_ok!(Crowdloan::change_association_with_relay_keys(
+			RuntimeOrigin::signed(1),
+			2,
+			1,
+			vec![proof(1), proof(2)],
+			3,
+			None
+		));
+		assert_eq!(Crowdloan::accounts_payable(&2).unwrap().total_reward, 1500);
+	});
+}
+
+#[test]
+fn delayed_reward_address_changes_can_be_cancelled_or_executed() {
+	empty().execute_with(|| {
+		let pairs = get_ed25519_pairs(1);
+		// The init relay block gets inserted
+		roll_to(2);
+		let init_block = Crowdloan::init_vesting_block();
+		assert_ok!(Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::root(),
+			vec![
+				(pairs[0].public().into(), Some(1), 500u32.into()),
+				([2u8; 32].into(), Some(2), 500u32.into()),
+				([3u8; 32].into(), None, 1500u32.into()),
+			]
+		));
+		assert_ok!(Crowdloan::complete_initialization(
+			RuntimeOrigin::root(),
+			init_block + VESTING
+		));
+
+		TestRewardAddressChangeDelay::set(5);
+		assert_ok!(Crowdloan::update_reward_address(
+			RuntimeOrigin::signed(1),
+			8,
+			1
+		));
+		let executable_at = System::block_number() + 5;
+		assert_eq!(
+			Crowdloan::pending_reward_address_changes(&1),
+			Some(PendingRewardAddressChange {
+				new_account: 8,
+				executable_at,
+			})
+		);
+		// Nothing moved yet
+		assert!(Crowdloan::accounts_payable(&1).is_some());
+		assert!(Crowdloan::accounts_payable(&8).is_none());
+		assert_noop!(
+			Crowdloan::update_reward_address(RuntimeOrigin::signed(1), 9, 1),
+			Error::<Test>::RewardAddressChangePending
+		);
+		assert_noop!(
+			Crowdloan::execute_pending_change(RuntimeOrigin::signed(9), 1, 1),
+			Error::<Test>::RewardAddressChangeNotExecutableYet
+		);
+
+		// The owner changes their mind
+		assert_ok!(Crowdloan::cancel_reward_address_change(
+			RuntimeOrigin::signed(1)
+		));
+		assert_noop!(
+			Crowdloan::cancel_reward_address_change(RuntimeOrigin::signed(1)),
+			Error::<Test>::NoPendingRewardAddressChange
+		);
+
+		// A contributing relay key stops the change made with the native key
+		assert_ok!(Crowdloan::update_reward_address(
+			RuntimeOrigin::signed(1),
+			8,
+			1
+		));
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+		payload.append(&mut CANCEL_CHANGE_BYTES.to_vec());
+		payload.append(&mut 1u64.encode());
+		payload.append(&mut 9u64.encode());
+		payload.append(&mut executable_at.encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+		assert_noop!(
+			Crowdloan::cancel_reward_address_change_with_relay_key(
+				RuntimeOrigin::signed(5),
+				1,
+				pairs[0].public().into(),
+				pairs[0].sign(&payload).into()
+			),
+			Error::<Test>::InvalidClaimSignature
+		);
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+		payload.append(&mut CANCEL_CHANGE_BYTES.to_vec());
+		payload.append(&mut 1u64.encode());
+		payload.append(&mut 8u64.encode());
+		payload.append(&mut executable_at.encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+		assert_noop!(
+			Crowdloan::cancel_reward_address_change_with_relay_key(
+				RuntimeOrigin::signed(5),
+				1,
+				[2u8; 32].into(),
+				pairs[0].sign(&payload).into()
+			),
+			Error::<Test>::NonContributedAddressProvided
+		);
+		assert_ok!(Crowdloan::cancel_reward_address_change_with_relay_key(
+			RuntimeOrigin::signed(5),
+			1,
+			pairs[0].public().into(),
+			pairs[0].sign(&payload).into()
+		));
+		assert!(Crowdloan::pending_reward_address_changes(&1).is_none());
+
+		// The signature does not cancel a change queued again later
+		roll_to(System::block_number() + 2);
+		assert_ok!(Crowdloan::update_reward_address(
+			RuntimeOrigin::signed(1),
+			8,
+			1
+		));
+		assert_noop!(
+			Crowdloan::cancel_reward_address_change_with_relay_key(
+				RuntimeOrigin::signed(5),
+				1,
+				pairs[0].public().into(),
+				pairs[0].sign(&payload).into()
+			),
+			Error::<Test>::InvalidClaimSignature
+		);
+
+		// Anyone can execute the change once the delay is over
+		let executable_at = Crowdloan::pending_reward_address_changes(&1)
+			.unwrap()
+			.executable_at;
+		roll_to(executable_at + 1);
+		assert_ok!(Crowdloan::execute_pending_change(
+			RuntimeOrigin::signed(9),
+			1,
+			1
+		));
+		assert!(Crowdloan::accounts_payable(&1).is_none());
+		assert_eq!(Crowdloan::accounts_payable(&8).unwrap().total_reward, 500);
+		assert!(Crowdloan::pending_reward_address_changes(&1).is_none());
+
+		let expected = vec![
+			crate::Event::RewardAddressChangeQueued {
+				previous_account: 1,
+				new_account: 8,
+				executable_at,
+			},
+			crate::Event::RewardAddressUpdated {
+				previous_account: 1,
+				new_account: 8,
+				mechanism: RewardAddressChangeMechanism::SelfUpdate,
+			},
+		];
+		assert_eq!(events()[events().len() - 2..], expected[..]);
+		assert!(
+			events().contains(&crate::Event::RewardAddressChangeCancelled {
+				previous_account: 1,
+				new_account: 8,
+				relay_account: Some(*pairs[0].public().as_array_ref()),
+			})
+		);
+		assert!(
+			events().contains(&crate::Event::RewardAddressChangeCancelled {
+				previous_account: 1,
+				new_account: 8,
+				relay_account: None,
+			})
+		);
+	});
+}
+
+#[test]
+fn contributions_of_several_relay_accounts_keep_adding_up_to_the_reward() {
+	empty().execute_with(|| {
+		// The init relay block gets inserted
+		roll_to(2);
+		let init_block = Crowdloan::init_vesting_block();
+		assert_ok!(Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::root(),
+			vec![
+				([1u8; 32].into(), Some(1), 500u32.into()),
+				([2u8; 32].into(), Some(1), 300u32.into()),
+				([3u8; 32].into(), None, 1700u32.into()),
+			]
+		));
+		assert_ok!(Crowdloan::complete_initialization(
+			RuntimeOrigin::root(),
+			init_block + VESTING
+		));
+		assert_ok!(Crowdloan::set_transfers_enabled(
+			RuntimeOrigin::root(),
+			true
+		));
+
+		// 160 claimed and 640 unclaimed, 52 + 211 are transferred
+		assert_ok!(Crowdloan::transfer_position(
+			RuntimeOrigin::signed(1),
+			9,
+			Perbill::from_percent(33)
+		));
+		let info = Crowdloan::accounts_payable(&1).unwrap();
+		assert_eq!(info.total_reward, 537);
+		assert_eq!(
+			info.contributed_relay_addresses,
+			vec![([1u8; 32], Some(335)), ([2u8; 32], Some(202))]
+		);
+
+		// There is no telling which of the contributions an adjustment is for
+		assert_ok!(Crowdloan::adjust_reward(
+			RuntimeOrigin::root(),
+			ContributorAccount::Native(1),
+			600u32.into()
+		));
+		assert_eq!(
+			Crowdloan::accounts_payable(&1)
+				.unwrap()
+				.contributed_relay_addresses,
+			vec![([1u8; 32], None), ([2u8; 32], None)]
+		);
+	});
+}
+
+#[test]
+fn split_and_transfer_can_not_bypass_the_reward_address_change_delay() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+		assert_ok!(Crowdloan::set_transfers_enabled(
+			RuntimeOrigin::root(),
+			true
+		));
+		TestRewardAddressChangeDelay::set(5);
+
+		// A compromised native key can not move the whole reward at once
+		assert_noop!(
+			Crowdloan::split_reward(RuntimeOrigin::signed(1), vec![(9, Perbill::one())], 1),
+			Error::<Test>::RewardAddressChangeDelayed
+		);
+		assert_noop!(
+			Crowdloan::transfer_position(RuntimeOrigin::signed(1), 9, Perbill::one()),
+			Error::<Test>::RewardAddressChangeDelayed
+		);
+
+		// Nor race a change that was queued before the delay was lifted
+		assert_ok!(Crowdloan::update_reward_address(
+			RuntimeOrigin::signed(1),
+			9,
+			1
+		));
+		TestRewardAddressChangeDelay::set(0);
+		assert_noop!(
+			Crowdloan::split_reward(RuntimeOrigin::signed(1), vec![(10, Perbill::one())], 1),
+			Error::<Test>::RewardAddressChangePending
+		);
+		assert_noop!(
+			Crowdloan::transfer_position(RuntimeOrigin::signed(1), 10, Perbill::one()),
+			Error::<Test>::RewardAddressChangePending
+		);
+
+		assert_ok!(Crowdloan::cancel_reward_address_change(
+			RuntimeOrigin::signed(1)
+		));
+		assert_ok!(Crowdloan::split_reward(
+			RuntimeOrigin::signed(1),
+			vec![(10, Perbill::one())],
+			1
+		));
+		assert_eq!(Crowdloan::accounts_payable(&10).unwrap().total_reward, 500);
+	});
+}
+
+#[test]
+fn lost_accounts_can_be_recovered_with_governance_approval() {
+	empty().execute_with(|| {
+		initialize_and_complete();
+		let evidence_hash = sp_core::H256::repeat_byte(7);
+
+		// 2 asks to recover the reward of 1 into 8
+		assert_ok!(Crowdloan::request_recovery(
+			RuntimeOrigin::signed(2),
+			1,
+			8,
+			evidence_hash
+		));
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert_noop!(
+			Crowdloan::request_recovery(RuntimeOrigin::signed(2), 1, 8, evidence_hash),
+			Error::<Test>::RecoveryAlreadyRequested
+		);
+		assert_noop!(
+			Crowdloan::finalize_recovery(RuntimeOrigin::signed(9), 1, 1),
+			Error::<Test>::RecoveryNotApproved
+		);
+		assert_noop!(
+			Crowdloan::cancel_recovery(RuntimeOrigin::signed(9), 1),
+			DispatchError::BadOrigin
+		);
+
+		// The owner of 1 still has the key, so the request is vetoed and the deposit slashed
+		assert_ok!(Crowdloan::cancel_recovery(RuntimeOrigin::signed(1), 1));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 90);
+		assert!(Crowdloan::recovery_requests(&1).is_none());
+
+		assert_ok!(Crowdloan::request_recovery(
+			RuntimeOrigin::signed(2),
+			1,
+			8,
+			evidence_hash
+		));
+		assert_noop!(
+			Crowdloan::approve_recovery(RuntimeOrigin::signed(2), 1),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Crowdloan::approve_recovery(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			Crowdloan::approve_recovery(RuntimeOrigin::root(), 1),
+			Error::<Test>::RecoveryAlreadyApproved
+		);
+		assert_noop!(
+			Crowdloan::finalize_recovery(RuntimeOrigin::signed(9), 1, 1),
+			Error::<Test>::RecoveryNotExecutableYet
+		);
+
+		// Anyone can finalize it once the delay is over
+		let executable_at = Crowdloan::recovery_requests(&1)
+			.unwrap()
+			.executable_at
+			.unwrap();
+		roll_to(executable_at + 1);
+		assert_ok!(Crowdloan::pause(
+			RuntimeOrigin::root(),
+			PausableCall::UpdateRewardAddress
+		));
+		assert_noop!(
+			Crowdloan::finalize_recovery(RuntimeOrigin::signed(9), 1, 1),
+			Error::<Test>::CallPaused
+		);
+		assert_ok!(Crowdloan::unpause(
+			RuntimeOrigin::root(),
+			PausableCall::UpdateRewardAddress
+		));
+		assert_ok!(Crowdloan::finalize_recovery(RuntimeOrigin::signed(9), 1, 1));
+		assert!(Crowdloan::accounts_payable(&1).is_none());
+		assert_eq!(Crowdloan::accounts_payable(&8).unwrap().total_reward, 500);
+		assert!(Crowdloan::recovery_requests(&1).is_none());
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 90);
+
+		let expected = vec![
+			crate::Event::RecoveryFinalized {
+				lost_account: 1,
+				new_account: 8,
+				evidence_hash,
+			},
+			crate::Event::RewardAddressUpdated {
+				previous_account: 1,
+				new_account: 8,
+				mechanism: RewardAddressChangeMechanism::Recovery,
+			},
+		];
+		assert_eq!(events()[events().len() - 2..], expected[..]);
+
+		// Requests that do not convince AdminOrigin lose the deposit too
+		assert_ok!(Crowdloan::request_recovery(
+			RuntimeOrigin::signed(2),
+			8,
+			9,
+			evidence_hash
+		));
+		assert_ok!(Crowdloan::reject_recovery(RuntimeOrigin::root(), 8));
+		assert_eq!(Balances::free_balance(2), 80);
+		assert_eq!(
+			events().last(),
+			Some(&crate::Event::RecoveryCancelled {
+				lost_account: 8,
+				slashed: 10,
+			})
+		);
+	});
+}
+
+#[test]
+fn contributions_from_relay_multisigs_can_be_associated() {
+	empty().execute_with(|| {
+		let pairs = get_ed25519_pairs(4);
+		let signatories: Vec<[u8; 32]> = pairs[0..3]
+			.iter()
+			.map(|pair| *pair.public().as_array_ref())
+			.collect();
+		// Signatories do not need to be sorted
+		let multisig = Crowdloan::multisig_account_id(&signatories, 2);
+		let mut sorted_signatories = signatories.clone();
+		sorted_signatories.sort();
+		assert_eq!(
+			Crowdloan::multisig_account_id(&sorted_signatories, 2),
+			multisig
+		);
+		assert!(Crowdloan::multisig_account_id(&signatories, 3) != multisig);
+
+		// The init relay block gets inserted
+		roll_to(2);
+		let init_block = Crowdloan::init_vesting_block();
+		assert_ok!(Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::root(),
+			vec![
+				(multisig.into(), None, 500u32.into()),
+				([2u8; 32].into(), None, 2000u32.into()),
+			]
+		));
+		assert_ok!(Crowdloan::complete_initialization(
+			RuntimeOrigin::root(),
+			init_block + VESTING
+		));
+
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+		payload.append(&mut 5u64.encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+		let proof = |i: usize| -> ([u8; 32], MultiSignature) {
+			(pairs[i].public().into(), pairs[i].sign(&payload).into())
+		};
+
+		assert_noop!(
+			Crowdloan::associate_native_identity_with_multisig(
+				RuntimeOrigin::signed(4),
+				5,
+				multisig,
+				signatories.clone(),
+				3,
+				vec![proof(0), proof(1), proof(2)],
+				None
+			),
+			Error::<Test>::InvalidMultisig
+		);
+		assert_noop!(
+			Crowdloan::associate_native_identity_with_multisig(
+				RuntimeOrigin::signed(4),
+				5,
+				multisig,
+				signatories.clone(),
+				0,
+				vec![],
+				None
+			),
+			Error::<Test>::InvalidMultisig
+		);
+		assert_noop!(
+			Crowdloan::associate_native_identity_with_multisig(
+				RuntimeOrigin::signed(4),
+				5,
+				multisig,
+				signatories.clone(),
+				2,
+				vec![proof(0), proof(3)],
+				None
+			),
+			Error::<Test>::NonContributedAddressProvided
+		);
+		// Repeated signatures only count once
+		assert_noop!(
+			Crowdloan::associate_native_identity_with_multisig(
+				RuntimeOrigin::signed(4),
+				5,
+				multisig,
+				signatories.clone(),
+				2,
+				vec![proof(0), proof(0)],
+				None
+			),
+			Error::<Test>::InsufficientNumberOfValidProofs
+		);
+
+		assert_ok!(Crowdloan::associate_native_identity_with_multisig(
+			RuntimeOrigin::signed(4),
+			5,
+			multisig,
+			signatories,
+			2,
+			vec![proof(2), proof(0)],
+			None
+		));
+		assert!(Crowdloan::unassociated_contributions(&multisig).is_none());
+		assert!(Crowdloan::claimed_relay_chain_ids(&multisig).is_some());
+		let info = Crowdloan::accounts_payable(&5).unwrap();
+		assert_eq!(info.total_reward, 500);
+		assert_eq!(info.claimed_reward, 100);
+		assert_eq!(
+			events().last(),
+			Some(&crate::Event::NativeIdentityAssociated {
+				relay_account: multisig,
+				account: 5,
+				total_reward: 500,
+			})
+		);
+	});
+}
+
+#[test]
+fn contributions_can_be_associated_by_a_relay_delegate() {
+	empty().execute_with(|| {
+		let pairs = get_ed25519_pairs(2);
+		let delegate: [u8; 32] = pairs[1].public().into();
+
+		// The init relay block gets inserted
+		roll_to(2);
+		let init_block = Crowdloan::init_vesting_block();
+		assert_ok!(Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::root(),
+			vec![
+				(pairs[0].public().into(), None, 500u32.into()),
+				([2u8; 32].into(), None, 2000u32.into()),
+			]
+		));
+		assert_ok!(Crowdloan::complete_initialization(
+			RuntimeOrigin::root(),
+			init_block + VESTING
+		));
+
+		let delegation_payload = |delegation: &RelayDelegation<[u8; 32], u64>| {
+			let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+			payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+			payload.append(&mut DELEGATION_BYTES.to_vec());
+			payload.append(&mut delegation.delegate.encode());
+			payload.append(&mut delegation.expires_at.encode());
+			payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+			payload
+		};
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+		payload.append(&mut 5u64.encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+		let proof: MultiSignature = pairs[1].sign(&payload).into();
+
+		// The delegation expires at the current vesting block
+		let expired = RelayDelegation {
+			delegate,
+			expires_at: <Test as Config>::VestingBlockProvider::current_block_number(),
+		};
+		assert_noop!(
+			Crowdloan::associate_native_identity_with_delegate(
+				RuntimeOrigin::signed(4),
+				5,
+				pairs[0].public().into(),
+				expired.clone(),
+				pairs[0].sign(&delegation_payload(&expired)).into(),
+				proof.clone(),
+				None
+			),
+			Error::<Test>::DelegationExpired
+		);
+
+		let delegation = RelayDelegation {
+			delegate,
+			expires_at: 100,
+		};
+		let delegation_proof: MultiSignature =
+			pairs[0].sign(&delegation_payload(&delegation)).into();
+		// The delegate can not sign the delegation itself
+		assert_noop!(
+			Crowdloan::associate_native_identity_with_delegate(
+				RuntimeOrigin::signed(4),
+				5,
+				pairs[0].public().into(),
+				delegation.clone(),
+				pairs[1].sign(&delegation_payload(&delegation)).into(),
+				proof.clone(),
+				None
+			),
+			Error::<Test>::InvalidDelegationSignature
+		);
+		// Only the signature of the delegate is accepted over the association
+		assert_noop!(
+			Crowdloan::associate_native_identity_with_delegate(
+				RuntimeOrigin::signed(4),
+				6,
+				pairs[0].public().into(),
+				delegation.clone(),
+				delegation_proof.clone(),
+				proof.clone(),
+				None
+			),
+			Error::<Test>::InvalidClaimSignature
+		);
+
+		assert_ok!(Crowdloan::associate_native_identity_with_delegate(
+			RuntimeOrigin::signed(4),
+			5,
+			pairs[0].public().into(),
+			delegation,
+			delegation_proof,
+			proof,
+			None
+		));
+		let info = Crowdloan::accounts_payable(&5).unwrap();
+		assert_eq!(info.total_reward, 500);
+		assert_eq!(info.claimed_reward, 100);
+		assert!(Crowdloan::claimed_relay_chain_ids(pairs[0].public().as_array_ref()).is_some());
+	});
+}
+
+#[test]
+fn associations_can_require_the_consent_of_the_reward_account() {
+	empty().execute_with(|| {
+		TestRequireNativeConsent::set(true);
+		let pairs = initialize_and_complete();
+		let association_payload = |account: u64| {
+			let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+			payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+			payload.append(&mut account.encode());
+			payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+			payload
+		};
+
+		// A valid relay signature is not enough if the reward account did not consent
+		let signature: MultiSignature = pairs[0].sign(&association_payload(3)).into();
+		assert_noop!(
+			Crowdloan::associate_native_identity(
+				RuntimeOrigin::signed(4),
+				3,
+				pairs[0].public().into(),
+				signature.clone(),
+				None
+			),
+			Error::<Test>::AssociationNotApproved
+		);
+		// The reward account can submit the association itself
+		assert_ok!(Crowdloan::associate_native_identity(
+			RuntimeOrigin::signed(3),
+			3,
+			pairs[0].public().into(),
+			signature,
+			None
+		));
+		assert_eq!(Crowdloan::accounts_payable(&3).unwrap().total_reward, 500);
+
+		// Only unassociated contributions can be approved
+		assert_noop!(
+			Crowdloan::approve_association(RuntimeOrigin::signed(5), [9u8; 32].into()),
+			Error::<Test>::NoAssociatedClaim
+		);
+		assert_noop!(
+			Crowdloan::approve_association(RuntimeOrigin::signed(5), pairs[0].public().into()),
+			Error::<Test>::NoAssociatedClaim
+		);
+
+		// Or approve it beforehand, and the approval is used up
+		assert_ok!(Crowdloan::approve_association(
+			RuntimeOrigin::signed(5),
+			pairs[1].public().into()
+		));
+		assert_ok!(Crowdloan::associate_native_identity(
+			RuntimeOrigin::signed(4),
+			5,
+			pairs[1].public().into(),
+			pairs[1].sign(&association_payload(5)).into(),
+			None
+		));
+		assert_eq!(Crowdloan::accounts_payable(&5).unwrap().total_reward, 500);
+		assert!(Crowdloan::association_approvals(&5, pairs[1].public().as_array_ref()).is_none());
+
+		// Approvals can be withdrawn
+		assert_ok!(Crowdloan::approve_association(
+			RuntimeOrigin::signed(6),
+			pairs[2].public().into()
+		));
+		assert_ok!(Crowdloan::cancel_association_approval(
+			RuntimeOrigin::signed(6),
+			pairs[2].public().into()
+		));
+		assert_noop!(
+			Crowdloan::cancel_association_approval(
+				RuntimeOrigin::signed(6),
+				pairs[2].public().into()
+			),
+			Error::<Test>::AssociationNotApproved
+		);
+		assert_noop!(
+			Crowdloan::associate_native_identity(
+				RuntimeOrigin::signed(4),
+				6,
+				pairs[2].public().into(),
+				pairs[2].sign(&association_payload(6)).into(),
+				None
+			),
+			Error::<Test>::AssociationNotApproved
+		);
+
+		// Or sign its consent, which needs no funds
+		let consent_payload = |relay_account: [u8; 32]| {
+			let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+			payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+			payload.append(&mut CONSENT_BYTES.to_vec());
+			payload.append(&mut relay_account.encode());
+			payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+			payload
+		};
+		let relay_account = *pairs[2].public().as_array_ref();
+		assert_noop!(
+			Crowdloan::associate_native_identity(
+				RuntimeOrigin::signed(4),
+				6,
+				pairs[2].public().into(),
+				pairs[2].sign(&association_payload(6)).into(),
+				Some(TestSignature(7, consent_payload(relay_account)))
+			),
+			Error::<Test>::InvalidConsentSignature
+		);
+		assert_noop!(
+			Crowdloan::associate_native_identity(
+				RuntimeOrigin::signed(4),
+				6,
+				pairs[2].public().into(),
+				pairs[2].sign(&association_payload(6)).into(),
+				Some(TestSignature(6, consent_payload([9u8; 32])))
+			),
+			Error::<Test>::InvalidConsentSignature
+		);
+		assert_ok!(Crowdloan::associate_native_identity(
+			RuntimeOrigin::signed(4),
+			6,
+			pairs[2].public().into(),
+			pairs[2].sign(&association_payload(6)).into(),
+			Some(TestSignature(6, consent_payload(relay_account)))
+		));
+		assert_eq!(Crowdloan::accounts_payable(&6).unwrap().total_reward, 500);
+	});
+}
+
+#[test]
+fn relay_key_changes_can_require_the_consent_of_the_new_account() {
+	empty().execute_with(|| {
+		TestRequireNativeConsent::set(true);
+		let pairs = get_ed25519_pairs(1);
+		// The init relay block gets inserted
+		roll_to(2);
+		let init_block = Crowdloan::init_vesting_block();
+		assert_ok!(Crowdloan::initialize_reward_vec(
+			RuntimeOrigin::root(),
+			vec![
+				(pairs[0].public().into(), Some(1), 500u32.into()),
+				([9u8; 32].into(), None, 2000u32.into()),
+			]
+		));
+		assert_ok!(Crowdloan::complete_initialization(
+			RuntimeOrigin::root(),
+			init_block + VESTING
+		));
+
+		let mut payload = WRAPPED_BYTES_PREFIX.to_vec();
+		payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+		payload.append(&mut 2u64.encode());
+		payload.append(&mut 1u64.encode());
+		payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+		let proofs: Vec<([u8; 32], MultiSignature)> =
+			vec![(pairs[0].public().into(), pairs[0].sign(&payload).into())];
+
+		let mut consent_payload = WRAPPED_BYTES_PREFIX.to_vec();
+		consent_payload.append(&mut TestSigantureNetworkIdentifier::get().to_vec());
+		consent_payload.append(&mut CONSENT_BYTES.to_vec());
+		consent_payload.append(&mut 1u64.encode());
+		consent_payload.append(&mut WRAPPED_BYTES_POSTFIX.to_vec());
+
+		// The relay keys alone can't move the reward to an account without a reward
+		assert_noop!(
+			Crowdloan::change_association_with_relay_keys(
+				RuntimeOrigin::signed(3),
+				2,
+				1,
+				proofs.clone(),
+				1,
+				None
+			),
+			Error::<Test>::AssociationNotApproved
+		);
+		assert_noop!(
+			Crowdloan::change_association_with_relay_keys(
+				RuntimeOrigin::signed(3),
+				2,
+				1,
+				proofs.clone(),
+				1,
+				Some(TestSignature(3, consent_payload.clone()))
+			),
+			Error::<Test>::InvalidConsentSignature
+		);
+
+		// The new account signs its consent to receive the reward of 1
+		assert_ok!(Crowdloan::change_association_with_relay_keys(
+			RuntimeOrigin::signed(3),
+			2,
+			1,
+			proofs,
+			1,
+			Some(TestSignature(2, consent_payload))
+		));
+		assert!(Crowdloan::accounts_payable(&1).is_none());
+		assert_eq!(Crowdloan::accounts_payable(&2).unwrap().total_reward, 500);
 	});
 }
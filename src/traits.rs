@@ -0,0 +1,68 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Traits through which the runtime plugs other pallets into the crowdloan rewards
+
+use frame_support::{dispatch::DispatchResult, weights::Weight};
+use sp_runtime::DispatchError;
+
+/// Hook called by claim_and_stake once the vested rewards are in the account of the contributor.
+///
+/// Runtimes implement it to forward the claimed funds into a staking or liquidity position
+/// described by the target. Returning an error reverts the whole claim.
+pub trait OnRewardClaimed<AccountId, Balance, Target> {
+	/// Forward amount, already transferred to who, into target
+	fn on_reward_claimed(who: &AccountId, amount: Balance, target: Target) -> DispatchResult;
+
+	/// Weight of forwarding the rewards into target, on top of the claim itself
+	fn weight(_target: &Target) -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Leaves the claimed rewards in the account of the contributor
+impl<AccountId, Balance, Target> OnRewardClaimed<AccountId, Balance, Target> for () {
+	fn on_reward_claimed(_who: &AccountId, _amount: Balance, _target: Target) -> DispatchResult {
+		Ok(())
+	}
+}
+
+/// Bonds rewards on behalf of contributors while they are still vesting.
+///
+/// The funds are already in the account of the contributor when bond is called. Implementations
+/// must keep them bonded until this pallet unbonds them, so they remain subject to vesting.
+pub trait RewardStaking<AccountId, Balance> {
+	/// Bond amount from the account of who
+	fn bond(who: &AccountId, amount: Balance) -> DispatchResult;
+
+	/// Release amount previously bonded for who
+	fn unbond(who: &AccountId, amount: Balance) -> DispatchResult;
+}
+
+/// Staking unvested rewards is not supported
+impl<AccountId, Balance> RewardStaking<AccountId, Balance> for () {
+	fn bond(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other(
+			"Staking unvested rewards is not supported",
+		))
+	}
+
+	fn unbond(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other(
+			"Staking unvested rewards is not supported",
+		))
+	}
+}
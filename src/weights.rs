@@ -39,6 +39,11 @@
 // --template=./benchmarking/frame-weight-template.hbs
 // --output
 // /tmp/
+//
+// Only the weights up to change_association_with_relay_keys come from the command above. The ones
+// from adjust_reward onwards are hand-written estimates, either conservative round figures or the
+// weight of a benchmarked call doing the same work, until the benchmarks added for them are run
+// with the same command.
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -54,9 +59,39 @@ pub trait WeightInfo {
 	fn initialize_reward_vec(x: u32) -> Weight;
 	fn complete_initialization() -> Weight;
 	fn claim() -> Weight;
-	fn update_reward_address() -> Weight;
+	fn update_reward_address(y: u32) -> Weight;
 	fn associate_native_identity() -> Weight;
-	fn change_association_with_relay_keys(x: u32) -> Weight;
+	fn change_association_with_relay_keys(x: u32, y: u32) -> Weight;
+	// Placeholders, not benchmarked yet
+	fn adjust_reward() -> Weight;
+	fn add_late_contributor() -> Weight;
+	fn remove_contributor(x: u32) -> Weight;
+	fn pause() -> Weight;
+	fn unpause() -> Weight;
+	fn claim_for() -> Weight;
+	fn claim_many(x: u32) -> Weight;
+	fn auto_payout() -> Weight;
+	fn claim_to() -> Weight;
+	fn claim_amount() -> Weight;
+	fn stake_unvested_rewards() -> Weight;
+	fn unbond_staked_rewards() -> Weight;
+	fn approve_merge() -> Weight;
+	fn cancel_merge_approval() -> Weight;
+	fn split_reward(x: u32, y: u32) -> Weight;
+	fn set_transfers_enabled() -> Weight;
+	fn transfer_position() -> Weight;
+	fn cancel_reward_address_change() -> Weight;
+	fn cancel_reward_address_change_with_relay_key() -> Weight;
+	fn execute_pending_change(y: u32) -> Weight;
+	fn request_recovery() -> Weight;
+	fn approve_recovery() -> Weight;
+	fn reject_recovery() -> Weight;
+	fn cancel_recovery() -> Weight;
+	fn finalize_recovery(y: u32) -> Weight;
+	fn associate_native_identity_with_multisig(x: u32) -> Weight;
+	fn associate_native_identity_with_delegate() -> Weight;
+	fn approve_association() -> Weight;
+	fn cancel_association_approval() -> Weight;
 }
 
 /// Weights for pallet_crowdloan_rewards using the Substrate node and recommended hardware.
@@ -82,18 +117,29 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_ref_time(101_484_000)
 			.saturating_add(T::DbWeight::get().reads(11))
 			.saturating_add(T::DbWeight::get().writes(5))
+			// FreeClaimsUsed, added by hand until this is benchmarked again
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
 	}
-	fn update_reward_address() -> Weight {
+	fn update_reward_address(y: u32) -> Weight {
 		Weight::from_ref_time(59_051_000)
 			.saturating_add(T::DbWeight::get().reads(6))
 			.saturating_add(T::DbWeight::get().writes(4))
+			// Merging relay addresses, added by hand until this is benchmarked again
+			.saturating_add(Weight::from_ref_time(
+				5_000_000_u64.saturating_mul(y as u64),
+			))
+			.saturating_add(T::DbWeight::get().reads_writes(y as u64, y as u64))
 	}
 	fn associate_native_identity() -> Weight {
 		Weight::from_ref_time(152_997_000)
 			.saturating_add(T::DbWeight::get().reads(9))
 			.saturating_add(T::DbWeight::get().writes(7))
+			// FreeClaimsUsed and AssociationApprovals, added by hand until this is benchmarked again
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+			// Verifying the consent signature, added by hand until this is benchmarked again
+			.saturating_add(Weight::from_ref_time(47_373_000))
 	}
-	fn change_association_with_relay_keys(x: u32) -> Weight {
+	fn change_association_with_relay_keys(x: u32, y: u32) -> Weight {
 		Weight::from_ref_time(0)
 			// Standard Error: 7_000
 			.saturating_add(Weight::from_ref_time(
@@ -101,6 +147,168 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			))
 			.saturating_add(T::DbWeight::get().reads(6))
 			.saturating_add(T::DbWeight::get().writes(4))
+			// Merging relay addresses, added by hand until this is benchmarked again
+			.saturating_add(Weight::from_ref_time(
+				5_000_000_u64.saturating_mul(y as u64),
+			))
+			.saturating_add(T::DbWeight::get().reads_writes(y as u64, y as u64))
+			// Verifying the consent signature, added by hand until this is benchmarked again
+			.saturating_add(Weight::from_ref_time(47_373_000))
+	}
+	// Placeholders, not benchmarked yet
+	fn adjust_reward() -> Weight {
+		Weight::from_ref_time(75_000_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	fn add_late_contributor() -> Weight {
+		// A single initialize_reward_vec row, plus funding the pot
+		Self::initialize_reward_vec(1)
+			.saturating_add(Weight::from_ref_time(50_000_000))
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+	fn remove_contributor(x: u32) -> Weight {
+		Weight::from_ref_time(75_000_000)
+			.saturating_add(Weight::from_ref_time(
+				5_000_000_u64.saturating_mul(x as u64),
+			))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(7))
+			.saturating_add(T::DbWeight::get().reads_writes(x as u64, x as u64))
+	}
+	fn pause() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn unpause() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn claim_for() -> Weight {
+		Self::claim()
+	}
+	fn claim_many(x: u32) -> Weight {
+		// One claim per account
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(Self::claim().saturating_mul(x as u64))
+	}
+	fn auto_payout() -> Weight {
+		Weight::from_ref_time(75_000_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	fn claim_to() -> Weight {
+		Self::claim()
+	}
+	fn claim_amount() -> Weight {
+		Self::claim()
+	}
+	fn stake_unvested_rewards() -> Weight {
+		Weight::from_ref_time(125_000_000)
+			.saturating_add(T::DbWeight::get().reads(12))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	fn unbond_staked_rewards() -> Weight {
+		Weight::from_ref_time(75_000_000)
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	fn approve_merge() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn cancel_merge_approval() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn split_reward(x: u32, y: u32) -> Weight {
+		Weight::from_ref_time(50_000_000)
+			.saturating_add(Weight::from_ref_time(
+				25_000_000_u64.saturating_mul(x as u64),
+			))
+			// Every slice holds every relay address
+			.saturating_add(Weight::from_ref_time(
+				5_000_000_u64
+					.saturating_mul(x as u64)
+					.saturating_mul(y as u64),
+			))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(x as u64)))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x as u64)))
+			.saturating_add(T::DbWeight::get().reads_writes(y as u64, y as u64))
+	}
+	fn set_transfers_enabled() -> Weight {
+		Weight::from_ref_time(25_000_000).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn transfer_position() -> Weight {
+		Weight::from_ref_time(50_000_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	fn cancel_reward_address_change() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn cancel_reward_address_change_with_relay_key() -> Weight {
+		// Verifying the relay signature is what change_association_with_relay_keys(1, 0) pays for
+		Self::change_association_with_relay_keys(1, 0)
+	}
+	fn execute_pending_change(y: u32) -> Weight {
+		// The same move as update_reward_address
+		Self::update_reward_address(y).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn request_recovery() -> Weight {
+		Weight::from_ref_time(50_000_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	fn approve_recovery() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn reject_recovery() -> Weight {
+		Weight::from_ref_time(50_000_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	fn cancel_recovery() -> Weight {
+		Weight::from_ref_time(50_000_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	fn finalize_recovery(y: u32) -> Weight {
+		// The same move as update_reward_address, plus releasing the deposit
+		Self::update_reward_address(y)
+			.saturating_add(Weight::from_ref_time(25_000_000))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	fn associate_native_identity_with_multisig(x: u32) -> Weight {
+		// One relay signature per signatory, on top of a regular association
+		Self::associate_native_identity()
+			.saturating_add(Self::change_association_with_relay_keys(x, 0))
+	}
+	fn associate_native_identity_with_delegate() -> Weight {
+		// The relay signature of the delegation and the native one of the delegate
+		Self::associate_native_identity()
+			.saturating_add(Self::change_association_with_relay_keys(1, 0))
+	}
+	fn approve_association() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn cancel_association_approval() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
 	}
 }
 
@@ -126,18 +334,29 @@ impl WeightInfo for () {
 		Weight::from_ref_time(101_484_000)
 			.saturating_add(RocksDbWeight::get().reads(11))
 			.saturating_add(RocksDbWeight::get().writes(5))
+			// FreeClaimsUsed, added by hand until this is benchmarked again
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
 	}
-	fn update_reward_address() -> Weight {
+	fn update_reward_address(y: u32) -> Weight {
 		Weight::from_ref_time(59_051_000)
 			.saturating_add(RocksDbWeight::get().reads(6))
 			.saturating_add(RocksDbWeight::get().writes(4))
+			// Merging relay addresses, added by hand until this is benchmarked again
+			.saturating_add(Weight::from_ref_time(
+				5_000_000_u64.saturating_mul(y as u64),
+			))
+			.saturating_add(RocksDbWeight::get().reads_writes(y as u64, y as u64))
 	}
 	fn associate_native_identity() -> Weight {
 		Weight::from_ref_time(152_997_000)
 			.saturating_add(RocksDbWeight::get().reads(9))
 			.saturating_add(RocksDbWeight::get().writes(7))
+			// FreeClaimsUsed and AssociationApprovals, added by hand until this is benchmarked again
+			.saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+			// Verifying the consent signature, added by hand until this is benchmarked again
+			.saturating_add(Weight::from_ref_time(47_373_000))
 	}
-	fn change_association_with_relay_keys(x: u32) -> Weight {
+	fn change_association_with_relay_keys(x: u32, y: u32) -> Weight {
 		Weight::from_ref_time(0)
 			// Standard Error: 7_000
 			.saturating_add(Weight::from_ref_time(
@@ -145,5 +364,167 @@ impl WeightInfo for () {
 			))
 			.saturating_add(RocksDbWeight::get().reads(6))
 			.saturating_add(RocksDbWeight::get().writes(4))
+			// Merging relay addresses, added by hand until this is benchmarked again
+			.saturating_add(Weight::from_ref_time(
+				5_000_000_u64.saturating_mul(y as u64),
+			))
+			.saturating_add(RocksDbWeight::get().reads_writes(y as u64, y as u64))
+			// Verifying the consent signature, added by hand until this is benchmarked again
+			.saturating_add(Weight::from_ref_time(47_373_000))
+	}
+	// Placeholders, not benchmarked yet
+	fn adjust_reward() -> Weight {
+		Weight::from_ref_time(75_000_000)
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn add_late_contributor() -> Weight {
+		// A single initialize_reward_vec row, plus funding the pot
+		Self::initialize_reward_vec(1)
+			.saturating_add(Weight::from_ref_time(50_000_000))
+			.saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+	}
+	fn remove_contributor(x: u32) -> Weight {
+		Weight::from_ref_time(75_000_000)
+			.saturating_add(Weight::from_ref_time(
+				5_000_000_u64.saturating_mul(x as u64),
+			))
+			.saturating_add(RocksDbWeight::get().reads(8))
+			.saturating_add(RocksDbWeight::get().writes(7))
+			.saturating_add(RocksDbWeight::get().reads_writes(x as u64, x as u64))
+	}
+	fn pause() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn unpause() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn claim_for() -> Weight {
+		Self::claim()
+	}
+	fn claim_many(x: u32) -> Weight {
+		// One claim per account
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(Self::claim().saturating_mul(x as u64))
+	}
+	fn auto_payout() -> Weight {
+		Weight::from_ref_time(75_000_000)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn claim_to() -> Weight {
+		Self::claim()
+	}
+	fn claim_amount() -> Weight {
+		Self::claim()
+	}
+	fn stake_unvested_rewards() -> Weight {
+		Weight::from_ref_time(125_000_000)
+			.saturating_add(RocksDbWeight::get().reads(12))
+			.saturating_add(RocksDbWeight::get().writes(7))
+	}
+	fn unbond_staked_rewards() -> Weight {
+		Weight::from_ref_time(75_000_000)
+			.saturating_add(RocksDbWeight::get().reads(7))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn approve_merge() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn cancel_merge_approval() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn split_reward(x: u32, y: u32) -> Weight {
+		Weight::from_ref_time(50_000_000)
+			.saturating_add(Weight::from_ref_time(
+				25_000_000_u64.saturating_mul(x as u64),
+			))
+			// Every slice holds every relay address
+			.saturating_add(Weight::from_ref_time(
+				5_000_000_u64
+					.saturating_mul(x as u64)
+					.saturating_mul(y as u64),
+			))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(x as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(x as u64)))
+			.saturating_add(RocksDbWeight::get().reads_writes(y as u64, y as u64))
+	}
+	fn set_transfers_enabled() -> Weight {
+		Weight::from_ref_time(25_000_000).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn transfer_position() -> Weight {
+		Weight::from_ref_time(50_000_000)
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn cancel_reward_address_change() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn cancel_reward_address_change_with_relay_key() -> Weight {
+		// Verifying the relay signature is what change_association_with_relay_keys(1, 0) pays for
+		Self::change_association_with_relay_keys(1, 0)
+	}
+	fn execute_pending_change(y: u32) -> Weight {
+		// The same move as update_reward_address
+		Self::update_reward_address(y).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn request_recovery() -> Weight {
+		Weight::from_ref_time(50_000_000)
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn approve_recovery() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn reject_recovery() -> Weight {
+		Weight::from_ref_time(50_000_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn cancel_recovery() -> Weight {
+		Weight::from_ref_time(50_000_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn finalize_recovery(y: u32) -> Weight {
+		// The same move as update_reward_address, plus releasing the deposit
+		Self::update_reward_address(y)
+			.saturating_add(Weight::from_ref_time(25_000_000))
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
+	fn associate_native_identity_with_multisig(x: u32) -> Weight {
+		// One relay signature per signatory, on top of a regular association
+		Self::associate_native_identity()
+			.saturating_add(Self::change_association_with_relay_keys(x, 0))
+	}
+	fn associate_native_identity_with_delegate() -> Weight {
+		// The relay signature of the delegation and the native one of the delegate
+		Self::associate_native_identity()
+			.saturating_add(Self::change_association_with_relay_keys(1, 0))
+	}
+	fn approve_association() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn cancel_association_approval() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
 	}
 }